@@ -61,6 +61,7 @@ class CfgESM
 		{ "taxes_territory_payment", "ESM_Taxes_TerritoryPayment" },
 		{ "taxes_territory_upgrade", "ESM_Taxes_TerritoryUpgrade" },
 		{ "territory_admin_uids", "ESM_TerritoryAdminUIDs" },
+		{ "territory_reminders_enabled", "ESM_TerritoryRemindersEnabled" },
 		{ "version", "ESM_Version" }
 	};
 };
@@ -0,0 +1,14 @@
+-- Adds `esm_territory_deletion` table.
+-- This table records territories deleted through ESM, who deleted them, and why.
+-- `command_restore` marks the record as restored when the territory is brought back.
+CREATE TABLE esm_territory_deletion (
+    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    territory_id INT UNSIGNED NOT NULL,
+    deleted_by_uid VARCHAR(32) NOT NULL,
+    reason TEXT,
+    deleted_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    restored_by_uid VARCHAR(32),
+    restored_at DATETIME,
+    INDEX idx_territory_id_restored_at (territory_id, restored_at),
+    CONSTRAINT fk_deleted_territory FOREIGN KEY (territory_id) REFERENCES territory (id) ON DELETE CASCADE ON UPDATE CASCADE
+) DEFAULT CHARSET = utf8mb4;
@@ -0,0 +1,11 @@
+-- Adds `esm_territory_reminder` table.
+-- This table tracks which payment reminders have been sent so each territory is only reminded
+-- once per lead time, per payment cycle. A cycle is identified by when the territory is due.
+CREATE TABLE esm_territory_reminder (
+    territory_id INT UNSIGNED NOT NULL,
+    lead_time_hours INT UNSIGNED NOT NULL,
+    due_at DATETIME NOT NULL,
+    reminded_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (territory_id, lead_time_hours, due_at),
+    CONSTRAINT fk_reminder_territory FOREIGN KEY (territory_id) REFERENCES territory (id) ON DELETE CASCADE ON UPDATE CASCADE
+) DEFAULT CHARSET = utf8mb4;
@@ -0,0 +1,13 @@
+-- Adds `esm_player_session` table.
+-- Exile's `player_history` only records deaths, so connect and disconnect events are stored
+-- here as sessions. A session without `disconnected_at` is still in progress.
+CREATE TABLE esm_player_session (
+    id INT UNSIGNED NOT NULL AUTO_INCREMENT,
+    account_uid VARCHAR(32) NOT NULL,
+    name VARCHAR(64) NOT NULL,
+    connected_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    disconnected_at DATETIME NULL DEFAULT NULL,
+    PRIMARY KEY (id),
+    INDEX idx_account_uid_connected_at (account_uid, connected_at),
+    CONSTRAINT fk_session_account FOREIGN KEY (account_uid) REFERENCES account (uid) ON DELETE CASCADE ON UPDATE CASCADE
+) DEFAULT CHARSET = utf8mb4;
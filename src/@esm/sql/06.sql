@@ -0,0 +1,26 @@
+-- Adds `deliver_at` to xm8_notification.
+-- Notifications created during the recipient's quiet hours are held until this time (UTC).
+ALTER TABLE xm8_notification
+ADD COLUMN `deliver_at` DATETIME NULL DEFAULT NULL AFTER `created_at`;
+
+-- Adds `esm_xm8_preference` table.
+-- Stores which XM8 notification types a player has turned off. Missing rows mean enabled.
+CREATE TABLE esm_xm8_preference (
+    recipient_uid VARCHAR(32) NOT NULL,
+    type VARCHAR(50) NOT NULL,
+    enabled TINYINT(1) NOT NULL DEFAULT 1,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+    PRIMARY KEY (recipient_uid, type),
+    CONSTRAINT fk_preference_account FOREIGN KEY (recipient_uid) REFERENCES account (uid) ON DELETE CASCADE ON UPDATE CASCADE
+) DEFAULT CHARSET = utf8mb4;
+
+-- Adds `esm_xm8_quiet_hours` table.
+-- Stores the time window (UTC) that a player does not want to receive XM8 notifications.
+-- The window may wrap past midnight, for example 22:00 to 07:00.
+CREATE TABLE esm_xm8_quiet_hours (
+    recipient_uid VARCHAR(32) NOT NULL,
+    starts_at TIME NOT NULL,
+    ends_at TIME NOT NULL,
+    PRIMARY KEY (recipient_uid),
+    CONSTRAINT fk_quiet_hours_account FOREIGN KEY (recipient_uid) REFERENCES account (uid) ON DELETE CASCADE ON UPDATE CASCADE
+) DEFAULT CHARSET = utf8mb4;
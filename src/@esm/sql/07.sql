@@ -0,0 +1,18 @@
+-- Adds `suppressed_count` to xm8_notification.
+-- How many duplicate or rate limited notifications were folded into this one.
+ALTER TABLE xm8_notification
+ADD COLUMN `suppressed_count` INT UNSIGNED NOT NULL DEFAULT 0 AFTER `content`;
+
+-- Adds `esm_xm8_suppressed_notification` table.
+-- Counts notifications that were held back after the last one was delivered. The count is
+-- folded into the next notification of the same type for the same territory.
+-- `territory_id` is 0 for notifications that aren't about a territory.
+CREATE TABLE esm_xm8_suppressed_notification (
+    recipient_uid VARCHAR(32) NOT NULL,
+    territory_id INT UNSIGNED NOT NULL DEFAULT 0,
+    type VARCHAR(50) NOT NULL,
+    suppressed_count INT UNSIGNED NOT NULL DEFAULT 0,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+    PRIMARY KEY (recipient_uid, territory_id, type),
+    CONSTRAINT fk_suppressed_notification_account FOREIGN KEY (recipient_uid) REFERENCES account (uid) ON DELETE CASCADE ON UPDATE CASCADE
+) DEFAULT CHARSET = utf8mb4;
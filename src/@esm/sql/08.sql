@@ -0,0 +1,18 @@
+-- Adds `esm_territory_custom_id_history` table.
+-- Records every change to a territory's custom ID. A previous custom ID keeps resolving to
+-- its territory for a while after it is changed, and only that territory can reclaim it
+-- during that time.
+CREATE TABLE esm_territory_custom_id_history (
+    id INT UNSIGNED NOT NULL AUTO_INCREMENT,
+    territory_id INT UNSIGNED NOT NULL,
+    previous_custom_id VARCHAR(100) NULL DEFAULT NULL,
+    custom_id VARCHAR(100) NOT NULL,
+    changed_by_uid VARCHAR(32) NOT NULL,
+    -- Changes made by territory admins don't start the owner's cooldown
+    changed_by_admin BOOLEAN NOT NULL DEFAULT FALSE,
+    changed_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (id),
+    INDEX idx_previous_custom_id (previous_custom_id),
+    INDEX idx_territory_id_changed_at (territory_id, changed_at),
+    CONSTRAINT fk_custom_id_history_territory FOREIGN KEY (territory_id) REFERENCES territory (id) ON DELETE CASCADE ON UPDATE CASCADE
+) DEFAULT CHARSET = utf8mb4;
@@ -0,0 +1,18 @@
+INSERT INTO
+    esm_territory_custom_id_history (
+        territory_id,
+        previous_custom_id,
+        custom_id,
+        changed_by_uid,
+        changed_by_admin
+    )
+SELECT
+    id,
+    esm_custom_id,
+    :custom_id,
+    :changed_by_uid,
+    :changed_by_admin
+FROM
+    territory
+WHERE
+    id = :territory_id
@@ -0,0 +1,4 @@
+INSERT IGNORE INTO
+    esm_territory_reminder (territory_id, lead_time_hours, due_at)
+VALUES
+    (:territory_id, :lead_time_hours, :due_at);
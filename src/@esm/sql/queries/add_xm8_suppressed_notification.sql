@@ -0,0 +1,6 @@
+INSERT INTO
+    esm_xm8_suppressed_notification (recipient_uid, territory_id, type, suppressed_count)
+VALUES
+    (:uid, :territory_id, :type, 1)
+ON DUPLICATE KEY UPDATE
+    suppressed_count = suppressed_count + 1
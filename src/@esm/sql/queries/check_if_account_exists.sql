@@ -0,0 +1,12 @@
+SELECT
+    CASE
+        WHEN EXISTS(
+            SELECT
+                uid
+            FROM
+                account
+            WHERE
+                uid = :uid
+        ) THEN 'true'
+        ELSE 'false'
+    END
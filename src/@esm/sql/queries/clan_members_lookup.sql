@@ -0,0 +1,9 @@
+SELECT
+    uid,
+    name
+FROM
+    account
+WHERE
+    clan_id = :clan_id
+ORDER BY
+    name
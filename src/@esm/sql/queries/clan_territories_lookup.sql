@@ -0,0 +1,12 @@
+SELECT
+    CONVERT(t.id, char) as id,
+    t.name,
+    t.owner_uid
+FROM
+    territory t
+    INNER JOIN account a ON a.uid = t.owner_uid
+WHERE
+    a.clan_id = :clan_id
+    AND t.deleted_at IS NULL
+ORDER BY
+    t.id
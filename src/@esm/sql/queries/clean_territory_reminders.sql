@@ -0,0 +1,3 @@
+DELETE FROM esm_territory_reminder
+WHERE
+    due_at < NOW() - INTERVAL 1 DAY;
@@ -0,0 +1,8 @@
+UPDATE
+    container o
+    INNER JOIN territory t ON t.id = o.territory_id
+SET
+    o.deleted_at = NOW()
+WHERE
+    o.deleted_at IS NULL
+    AND t.deleted_at IS NOT NULL
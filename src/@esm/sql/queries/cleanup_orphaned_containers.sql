@@ -0,0 +1,7 @@
+DELETE o
+FROM
+    container o
+    LEFT JOIN territory t ON t.id = o.territory_id
+WHERE
+    o.territory_id IS NOT NULL
+    AND t.id IS NULL
@@ -0,0 +1,10 @@
+DELETE x
+FROM
+    xm8_notification x
+    LEFT JOIN territory t ON t.id = x.territory_id
+WHERE
+    x.territory_id IS NOT NULL
+    AND (
+        t.id IS NULL
+        OR t.deleted_at IS NOT NULL
+    )
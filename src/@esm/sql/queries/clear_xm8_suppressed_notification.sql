@@ -0,0 +1,6 @@
+DELETE FROM
+    esm_xm8_suppressed_notification
+WHERE
+    recipient_uid = :uid
+    AND territory_id = :territory_id
+    AND type = :type
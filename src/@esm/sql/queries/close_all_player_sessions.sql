@@ -0,0 +1,5 @@
+UPDATE esm_player_session
+SET
+    disconnected_at = connected_at
+WHERE
+    disconnected_at IS NULL;
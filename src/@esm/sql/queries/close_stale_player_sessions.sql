@@ -0,0 +1,6 @@
+UPDATE esm_player_session
+SET
+    disconnected_at = connected_at
+WHERE
+    account_uid = :uid
+    AND disconnected_at IS NULL;
@@ -3,7 +3,45 @@ SELECT
     t.esm_custom_id,
     t.name,
     t.owner_uid,
-    a.name as owner_name
+    a.name as owner_name,
+    t.level,
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            construction c
+        WHERE
+            c.territory_id = t.id
+    ) as object_count,
+    t.last_paid_at,
+    :sort_expression as sort_value
 FROM
     territory t
     INNER JOIN account a ON a.uid = t.owner_uid
+WHERE
+    t.deleted_at IS NULL
+    AND (
+        :owner_uid IS NULL
+        OR t.owner_uid = :owner_uid
+    )
+    AND (
+        :min_level IS NULL
+        OR t.level >= :min_level
+    )
+    AND (
+        :overdue = 0
+        OR COALESCE(t.last_paid_at, t.created_at) < NOW() - INTERVAL :territory_lifetime DAY
+    )
+    AND (
+        :cursor_id IS NULL
+        OR :sort_expression :cursor_operator :cursor_value
+        OR (
+            :sort_expression = :cursor_value
+            AND t.id :cursor_operator :cursor_id
+        )
+    )
+ORDER BY
+    :sort_expression :sort_direction,
+    t.id :sort_direction
+LIMIT
+    :limit
@@ -0,0 +1,19 @@
+SELECT
+    c.id,
+    c.name,
+    c.leader_uid,
+    a.name as leader_name,
+    c.created_at,
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            clan_map_marker m
+        WHERE
+            m.clan_id = c.id
+    ) as map_marker_count
+FROM
+    clan c
+    LEFT JOIN account a ON a.uid = c.leader_uid
+WHERE
+    c.id = :clan_id
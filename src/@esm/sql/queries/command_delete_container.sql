@@ -0,0 +1,7 @@
+UPDATE
+    container
+SET
+    deleted_at = NOW()
+WHERE
+    territory_id = :territory_id
+    AND deleted_at IS NULL;
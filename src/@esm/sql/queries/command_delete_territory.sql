@@ -0,0 +1,7 @@
+UPDATE
+    territory
+SET
+    deleted_at = NOW()
+WHERE
+    id = :territory_id
+    AND deleted_at IS NULL;
@@ -0,0 +1,4 @@
+INSERT INTO
+    esm_territory_deletion (territory_id, deleted_by_uid, reason)
+VALUES
+    (:territory_id, :deleted_by_uid, :reason);
@@ -0,0 +1,18 @@
+SELECT
+    uid,
+    name,
+    last_connect_at
+FROM
+    account
+WHERE
+    uid LIKE :uid_prefix
+    OR LOWER(name) LIKE :wildcard_name
+    OR SOUNDEX(name) = SOUNDEX(:name)
+    OR (
+        LOWER(name) LIKE :first_character
+        AND CHAR_LENGTH(name) BETWEEN :min_length AND :max_length
+    )
+ORDER BY
+    last_connect_at DESC
+LIMIT
+    :max_candidates
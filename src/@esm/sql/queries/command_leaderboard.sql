@@ -0,0 +1,35 @@
+SELECT
+    a.uid,
+    a.name,
+    c.name as clan_name,
+    a.score,
+    a.kills,
+    a.deaths,
+    ROUND(a.kills / GREATEST(a.deaths, 1), 2) as kd_ratio,
+    a.locker,
+    COALESCE(p.money, 0) as money,
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            territory t
+        WHERE
+            t.owner_uid = a.uid
+            AND t.deleted_at IS NULL
+    ) as territory_count
+FROM
+    account a
+    LEFT JOIN player p ON p.account_uid = a.uid
+    LEFT JOIN clan c ON c.id = a.clan_id
+WHERE
+    (
+        :clan_id IS NULL
+        OR a.clan_id = :clan_id
+    )
+ORDER BY
+    :stat DESC,
+    a.uid ASC
+LIMIT
+    :limit
+OFFSET
+    :offset
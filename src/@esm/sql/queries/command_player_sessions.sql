@@ -0,0 +1,20 @@
+SELECT
+    COUNT(*) as session_count,
+    CAST(
+        COALESCE(
+            SUM(
+                TIMESTAMPDIFF(
+                    SECOND,
+                    connected_at,
+                    COALESCE(disconnected_at, NOW())
+                )
+            ),
+            0
+        ) AS UNSIGNED
+    ) as playtime_seconds,
+    MAX(COALESCE(disconnected_at, NOW())) as last_seen_at,
+    CAST(COALESCE(SUM(disconnected_at IS NULL), 0) AS UNSIGNED) as open_session_count
+FROM
+    esm_player_session
+WHERE
+    account_uid = :uid;
@@ -0,0 +1,22 @@
+SELECT
+    v.id,
+    v.class,
+    v.nickname,
+    v.account_uid,
+    v.position_x,
+    v.position_y,
+    v.position_z,
+    v.damage,
+    v.fuel,
+    v.is_locked,
+    CONVERT(v.territory_id, char) as territory_id,
+    t.name as territory_name,
+    v.last_updated_at
+FROM
+    vehicle v
+    LEFT JOIN territory t ON t.id = v.territory_id
+WHERE
+    v.account_uid = :uid
+    AND v.deleted_at IS NULL
+ORDER BY
+    v.id
@@ -0,0 +1,6 @@
+UPDATE account
+SET
+    clan_id = NULL
+WHERE
+    uid = :uid
+    AND clan_id = :clan_id
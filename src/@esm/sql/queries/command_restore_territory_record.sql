@@ -0,0 +1,7 @@
+UPDATE
+    esm_territory_deletion
+SET
+    restored_by_uid = :restored_by_uid,
+    restored_at = NOW()
+WHERE
+    id = :id;
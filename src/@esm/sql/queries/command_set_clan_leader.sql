@@ -0,0 +1,5 @@
+UPDATE clan
+SET
+    leader_uid = :leader_uid
+WHERE
+    id = :clan_id
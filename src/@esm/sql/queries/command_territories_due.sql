@@ -0,0 +1,21 @@
+SELECT
+    CONVERT(t.id, char) as id,
+    t.name,
+    t.owner_uid,
+    a.name as owner_name,
+    t.build_rights,
+    t.last_paid_at,
+    t.last_paid_at + INTERVAL :territory_lifetime DAY as due_at,
+    TIMESTAMPDIFF(
+        MINUTE,
+        NOW(),
+        t.last_paid_at + INTERVAL :territory_lifetime DAY
+    ) as minutes_remaining
+FROM
+    territory t
+    INNER JOIN account a ON a.uid = t.owner_uid
+WHERE
+    t.deleted_at IS NULL
+    AND t.last_paid_at + INTERVAL :territory_lifetime DAY BETWEEN NOW() AND NOW() + INTERVAL :within_hours HOUR
+ORDER BY
+    due_at
@@ -0,0 +1,7 @@
+UPDATE territory
+SET
+    owner_uid = :owner_uid,
+    build_rights = :build_rights,
+    moderators = :moderators
+WHERE
+    id = :territory_id
@@ -0,0 +1,6 @@
+UPDATE vehicle
+SET
+    account_uid = :new_owner_uid
+WHERE
+    id = :vehicle_id
+    AND deleted_at IS NULL
@@ -0,0 +1,9 @@
+SELECT
+    type
+FROM
+    esm_xm8_preference
+WHERE
+    recipient_uid = :uid
+    AND enabled = 0
+ORDER BY
+    type
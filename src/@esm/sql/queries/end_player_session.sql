@@ -0,0 +1,6 @@
+UPDATE esm_player_session
+SET
+    disconnected_at = NOW()
+WHERE
+    account_uid = :uid
+    AND disconnected_at IS NULL;
@@ -0,0 +1,3 @@
+DELETE FROM account
+WHERE
+    uid = :uid
@@ -0,0 +1,26 @@
+UPDATE clan c
+SET
+    c.leader_uid = (
+        SELECT
+            a.uid
+        FROM
+            account a
+        WHERE
+            a.clan_id = c.id
+            AND a.uid != :uid
+        ORDER BY
+            a.first_connect_at
+        LIMIT
+            1
+    )
+WHERE
+    c.leader_uid = :uid
+    AND EXISTS (
+        SELECT
+            1
+        FROM
+            account a
+        WHERE
+            a.clan_id = c.id
+            AND a.uid != :uid
+    )
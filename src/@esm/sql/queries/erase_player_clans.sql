@@ -0,0 +1,3 @@
+DELETE FROM clan
+WHERE
+    leader_uid = :uid
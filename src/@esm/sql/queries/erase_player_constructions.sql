@@ -0,0 +1,3 @@
+DELETE FROM construction
+WHERE
+    account_uid = :uid
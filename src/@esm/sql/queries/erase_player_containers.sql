@@ -0,0 +1,3 @@
+DELETE FROM container
+WHERE
+    account_uid = :uid
@@ -0,0 +1,155 @@
+SELECT
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            clan c
+        WHERE
+            c.leader_uid = :uid
+            AND EXISTS (
+                SELECT
+                    1
+                FROM
+                    account a
+                WHERE
+                    a.clan_id = c.id
+                    AND a.uid != :uid
+            )
+    ) as clan_leadership,
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            construction c
+            INNER JOIN territory t ON t.id = c.territory_id
+        WHERE
+            c.account_uid = :uid
+            AND t.owner_uid != :uid
+    ) as shared_constructions,
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            container c
+            INNER JOIN territory t ON t.id = c.territory_id
+        WHERE
+            c.account_uid = :uid
+            AND t.owner_uid != :uid
+    ) as shared_containers,
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            xm8_notification
+        WHERE
+            recipient_uid = :uid
+    ) as xm8_notifications,
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            esm_xm8_preference
+        WHERE
+            recipient_uid = :uid
+    ) as xm8_preferences,
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            esm_xm8_quiet_hours
+        WHERE
+            recipient_uid = :uid
+    ) as xm8_quiet_hours,
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            esm_xm8_suppressed_notification
+        WHERE
+            recipient_uid = :uid
+    ) as xm8_suppressed_notifications,
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            esm_player_session
+        WHERE
+            account_uid = :uid
+    ) as sessions,
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            player_history
+        WHERE
+            account_uid = :uid
+    ) as player_history,
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            player
+        WHERE
+            account_uid = :uid
+    ) as player,
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            vehicle
+        WHERE
+            account_uid = :uid
+    ) as vehicles,
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            territory
+        WHERE
+            owner_uid = :uid
+    ) as territories,
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            construction c
+            LEFT JOIN territory t ON t.id = c.territory_id
+        WHERE
+            c.account_uid = :uid
+            AND t.id IS NULL
+    ) as constructions,
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            container c
+            LEFT JOIN territory t ON t.id = c.territory_id
+        WHERE
+            c.account_uid = :uid
+            AND t.id IS NULL
+    ) as containers,
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            clan c
+        WHERE
+            c.leader_uid = :uid
+            AND NOT EXISTS (
+                SELECT
+                    1
+                FROM
+                    account a
+                WHERE
+                    a.clan_id = c.id
+                    AND a.uid != :uid
+            )
+    ) as clans,
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            account
+        WHERE
+            uid = :uid
+    ) as account
@@ -0,0 +1,3 @@
+DELETE FROM player_history
+WHERE
+    account_uid = :uid
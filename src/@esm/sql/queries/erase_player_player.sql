@@ -0,0 +1,3 @@
+DELETE FROM player
+WHERE
+    account_uid = :uid
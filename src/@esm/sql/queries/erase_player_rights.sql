@@ -0,0 +1,6 @@
+UPDATE territory
+SET
+    build_rights = :build_rights,
+    moderators = :moderators
+WHERE
+    id = :territory_id
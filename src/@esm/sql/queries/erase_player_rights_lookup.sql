@@ -0,0 +1,12 @@
+SELECT
+    id,
+    build_rights,
+    moderators
+FROM
+    territory
+WHERE
+    owner_uid != :uid
+    AND (
+        build_rights LIKE :wildcard_uid
+        OR moderators LIKE :wildcard_uid
+    )
@@ -0,0 +1,3 @@
+DELETE FROM esm_player_session
+WHERE
+    account_uid = :uid
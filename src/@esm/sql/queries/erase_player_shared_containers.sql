@@ -0,0 +1,7 @@
+UPDATE container c
+INNER JOIN territory t ON t.id = c.territory_id
+SET
+    c.account_uid = NULL
+WHERE
+    c.account_uid = :uid
+    AND t.owner_uid != :uid
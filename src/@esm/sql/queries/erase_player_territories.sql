@@ -0,0 +1,3 @@
+DELETE FROM territory
+WHERE
+    owner_uid = :uid
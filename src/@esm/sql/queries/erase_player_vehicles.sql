@@ -0,0 +1,3 @@
+DELETE FROM vehicle
+WHERE
+    account_uid = :uid
@@ -0,0 +1,3 @@
+DELETE FROM xm8_notification
+WHERE
+    recipient_uid = :uid
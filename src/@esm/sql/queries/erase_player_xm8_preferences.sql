@@ -0,0 +1,3 @@
+DELETE FROM esm_xm8_preference
+WHERE
+    recipient_uid = :uid
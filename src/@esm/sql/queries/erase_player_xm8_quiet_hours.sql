@@ -0,0 +1,3 @@
+DELETE FROM esm_xm8_quiet_hours
+WHERE
+    recipient_uid = :uid
@@ -0,0 +1,3 @@
+DELETE FROM esm_xm8_suppressed_notification
+WHERE
+    recipient_uid = :uid
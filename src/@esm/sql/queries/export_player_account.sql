@@ -0,0 +1,6 @@
+SELECT
+    *
+FROM
+    account
+WHERE
+    uid = :uid
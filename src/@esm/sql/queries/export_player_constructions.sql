@@ -0,0 +1,6 @@
+SELECT
+    *
+FROM
+    construction
+WHERE
+    account_uid = :uid
@@ -0,0 +1,6 @@
+SELECT
+    *
+FROM
+    container
+WHERE
+    account_uid = :uid
@@ -0,0 +1,6 @@
+SELECT
+    *
+FROM
+    player_history
+WHERE
+    account_uid = :uid
@@ -0,0 +1,6 @@
+SELECT
+    *
+FROM
+    player
+WHERE
+    account_uid = :uid
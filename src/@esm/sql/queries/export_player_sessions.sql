@@ -0,0 +1,6 @@
+SELECT
+    *
+FROM
+    esm_player_session
+WHERE
+    account_uid = :uid
@@ -0,0 +1,8 @@
+SELECT
+    *
+FROM
+    territory
+WHERE
+    owner_uid = :uid
+    OR build_rights LIKE :wildcard_uid
+    OR moderators LIKE :wildcard_uid
@@ -0,0 +1,6 @@
+SELECT
+    *
+FROM
+    vehicle
+WHERE
+    account_uid = :uid
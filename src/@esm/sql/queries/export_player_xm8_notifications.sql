@@ -0,0 +1,6 @@
+SELECT
+    *
+FROM
+    xm8_notification
+WHERE
+    recipient_uid = :uid
@@ -0,0 +1,6 @@
+SELECT
+    *
+FROM
+    esm_xm8_preference
+WHERE
+    recipient_uid = :uid
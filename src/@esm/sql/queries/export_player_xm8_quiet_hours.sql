@@ -0,0 +1,6 @@
+SELECT
+    *
+FROM
+    esm_xm8_quiet_hours
+WHERE
+    recipient_uid = :uid
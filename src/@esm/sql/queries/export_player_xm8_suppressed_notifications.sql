@@ -0,0 +1,6 @@
+SELECT
+    *
+FROM
+    esm_xm8_suppressed_notification
+WHERE
+    recipient_uid = :uid
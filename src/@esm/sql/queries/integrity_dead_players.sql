@@ -0,0 +1,7 @@
+SELECT
+    COUNT(*) as count,
+    SUBSTRING_INDEX(GROUP_CONCAT(id ORDER BY id), ',', 10) as sample_ids
+FROM
+    player
+WHERE
+    damage = 1
@@ -0,0 +1,13 @@
+SELECT
+    COUNT(*) as count,
+    SUBSTRING_INDEX(GROUP_CONCAT(o.id ORDER BY o.id), ',', 10) as sample_ids
+FROM
+    container o
+    LEFT JOIN territory t ON t.id = o.territory_id
+WHERE
+    o.territory_id IS NOT NULL
+    AND o.deleted_at IS NULL
+    AND (
+        t.id IS NULL
+        OR t.deleted_at IS NOT NULL
+    )
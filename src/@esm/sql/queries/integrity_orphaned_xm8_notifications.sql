@@ -0,0 +1,12 @@
+SELECT
+    COUNT(*) as count,
+    SUBSTRING_INDEX(GROUP_CONCAT(x.id ORDER BY x.id), ',', 10) as sample_ids
+FROM
+    xm8_notification x
+    LEFT JOIN territory t ON t.id = x.territory_id
+WHERE
+    x.territory_id IS NOT NULL
+    AND (
+        t.id IS NULL
+        OR t.deleted_at IS NOT NULL
+    )
@@ -0,0 +1,7 @@
+UPDATE
+    xm8_notification
+SET
+    suppressed_count = suppressed_count + 1
+WHERE
+    id = :id
+    AND state = "new"
@@ -0,0 +1,6 @@
+SELECT
+    clan_id
+FROM
+    account
+WHERE
+    uid = :uid
@@ -0,0 +1,18 @@
+SELECT
+    name,
+    connected_at,
+    disconnected_at,
+    TIMESTAMPDIFF(
+        SECOND,
+        connected_at,
+        COALESCE(disconnected_at, NOW())
+    ) as duration_seconds
+FROM
+    esm_player_session
+WHERE
+    account_uid = :uid
+ORDER BY
+    connected_at DESC,
+    id DESC
+LIMIT
+    :limit;
@@ -0,0 +1,6 @@
+INSERT INTO
+    esm_xm8_preference (recipient_uid, type, enabled)
+VALUES
+    (:uid, :type, :enabled)
+ON DUPLICATE KEY UPDATE
+    enabled = VALUES(enabled)
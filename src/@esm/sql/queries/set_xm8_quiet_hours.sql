@@ -0,0 +1,7 @@
+INSERT INTO
+    esm_xm8_quiet_hours (recipient_uid, starts_at, ends_at)
+VALUES
+    (:uid, :starts_at, :ends_at)
+ON DUPLICATE KEY UPDATE
+    starts_at = VALUES(starts_at),
+    ends_at = VALUES(ends_at)
@@ -0,0 +1,9 @@
+INSERT INTO
+    esm_player_session (account_uid, name)
+SELECT
+    uid,
+    :name
+FROM
+    account
+WHERE
+    uid = :uid;
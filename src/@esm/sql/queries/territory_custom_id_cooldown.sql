@@ -0,0 +1,7 @@
+SELECT
+    TIMESTAMPDIFF(SECOND, MAX(changed_at), NOW()) as seconds_since_change
+FROM
+    esm_territory_custom_id_history
+WHERE
+    territory_id = :territory_id
+    AND changed_by_admin = FALSE
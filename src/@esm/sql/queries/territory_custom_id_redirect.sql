@@ -0,0 +1,13 @@
+SELECT
+    h.territory_id
+FROM
+    esm_territory_custom_id_history h
+    INNER JOIN territory t ON t.id = h.territory_id
+WHERE
+    h.previous_custom_id = :custom_id
+    AND h.changed_at > NOW() - INTERVAL :redirect_days DAY
+    AND t.deleted_at IS NULL
+ORDER BY
+    h.changed_at DESC
+LIMIT
+    1
@@ -0,0 +1,14 @@
+SELECT
+    id,
+    deleted_by_uid,
+    reason,
+    deleted_at
+FROM
+    esm_territory_deletion
+WHERE
+    territory_id = :territory_id
+    AND restored_at IS NULL
+ORDER BY
+    id DESC
+LIMIT
+    1;
@@ -0,0 +1,10 @@
+SELECT
+    owner_uid,
+    name,
+    build_rights,
+    moderators
+FROM
+    territory
+WHERE
+    id = :territory_id
+    AND deleted_at IS NULL
@@ -0,0 +1,8 @@
+SELECT
+    COUNT(*) > 0 as reminded
+FROM
+    esm_territory_reminder
+WHERE
+    territory_id = :territory_id
+    AND lead_time_hours = :lead_time_hours
+    AND due_at = :due_at;
@@ -0,0 +1,7 @@
+SELECT
+    account_uid
+FROM
+    vehicle
+WHERE
+    id = :vehicle_id
+    AND deleted_at IS NULL
@@ -0,0 +1,8 @@
+SELECT
+    recipient_uid
+FROM
+    esm_xm8_preference
+WHERE
+    type = ?
+    AND enabled = 0
+    AND recipient_uid IN (:uids)
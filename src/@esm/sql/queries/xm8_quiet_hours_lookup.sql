@@ -0,0 +1,8 @@
+SELECT
+    recipient_uid,
+    starts_at,
+    ends_at
+FROM
+    esm_xm8_quiet_hours
+WHERE
+    recipient_uid IN (:uids)
@@ -0,0 +1,11 @@
+SELECT
+    recipient_uid,
+    COUNT(*) as notification_count
+FROM
+    xm8_notification
+WHERE
+    territory_id = ?
+    AND created_at > NOW() - INTERVAL ? SECOND
+    AND recipient_uid IN (:uids)
+GROUP BY
+    recipient_uid
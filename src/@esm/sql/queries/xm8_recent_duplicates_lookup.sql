@@ -0,0 +1,13 @@
+SELECT
+    recipient_uid,
+    id,
+    state
+FROM
+    xm8_notification
+WHERE
+    type = ?
+    AND territory_id <=> ?
+    AND created_at > NOW() - INTERVAL ? SECOND
+    AND recipient_uid IN (:uids)
+ORDER BY
+    id DESC
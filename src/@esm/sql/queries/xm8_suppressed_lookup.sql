@@ -0,0 +1,9 @@
+SELECT
+    recipient_uid,
+    suppressed_count
+FROM
+    esm_xm8_suppressed_notification
+WHERE
+    territory_id = ?
+    AND type = ?
+    AND recipient_uid IN (:uids)
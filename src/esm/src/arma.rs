@@ -19,6 +19,11 @@ pub fn is_territory_admin(steam_uid: &str) -> bool {
     lock!(TERRITORY_ADMINS).contains(&steam_uid.to_string())
 }
 
+#[cfg(test)]
+pub fn add_territory_admin(steam_uid: &str) {
+    lock!(TERRITORY_ADMINS).insert(steam_uid.to_string());
+}
+
 pub async fn initialize(receiver: UnboundedReceiver<ArmaRequest>) {
     trace!("[initialize] Loading threads");
     request_thread(receiver).await;
@@ -155,12 +160,27 @@ async fn post_initialization(mut message: Message) -> MessageResult {
 
     data.insert("extdb_version".to_owned(), json!(DATABASE.extdb_version));
 
+    // Exile's own protection money reminder stands down while ESM sends them
+    data.insert(
+        "territory_reminders_enabled".to_owned(),
+        json!(!crate::CONFIG.territory_reminder_lead_hours.is_empty()),
+    );
+
     info!("[post_init] Caching data...");
 
     // Store the territory admins
     *lock!(TERRITORY_ADMINS) =
         HashSet::from_iter(territory_admin_uids.iter().cloned());
 
+    // Any session that is still open was left behind when the server went down
+    match DATABASE.close_player_sessions().await {
+        Ok(0) => {}
+        Ok(count) => {
+            info!("[post_init] Closed {count} player sessions that were left open")
+        }
+        Err(e) => warn!("[post_init] Failed to close player sessions. {e}"),
+    }
+
     info!("[post_init] Updating Arma global variables...");
 
     send_to_arma(message)?;
@@ -226,16 +246,55 @@ async fn file_search(message: Message) -> MessageResult {
         message.id, search
     );
 
-    match log_search::search_files(&search).await {
-        Ok(results) => {
-            let message = message
-                .set_type(Type::Ack)
-                .set_data(Data::from([("results".to_owned(), json!(results))]));
+    let options = log_search::SearchOptions::from_data(&message.data)?;
 
-            Ok(Some(message))
+    let stream = message
+        .data
+        .get("stream")
+        .is_some_and(|v| *v == true || *v == "true");
+
+    // Bots that ask to stream receive the results as search_chunk messages as they are
+    // found, so only the final acknowledgement answers the search. Otherwise, every result
+    // is sent with the acknowledgement
+    let mut results = vec![];
+    let mut chunks = 0;
+    let summary = log_search::search_files(search, options, |chunk| {
+        if !stream {
+            results.extend(chunk);
+            return Ok(());
         }
-        Err(e) => Err(e.into()),
-    }
+
+        chunks += 1;
+
+        BotRequest::send(
+            Message::new()
+                .set_type(Type::SearchChunk)
+                .set_data(Data::from([
+                    ("search_id".to_owned(), json!(message.id)),
+                    ("results".to_owned(), json!(chunk)),
+                    ("chunk".to_owned(), json!(chunks)),
+                ])),
+        )
+        .map_err(|e| e.error_content)
+    })
+    .await?;
+
+    info!(
+        "[file_search] {} - found {} results in {} files across {} chunks. Truncated: {}",
+        message.id,
+        summary.total_results,
+        summary.files_searched,
+        chunks,
+        summary.truncated
+    );
+
+    let message = message.set_type(Type::Ack).set_data(Data::from([
+        ("results".to_owned(), json!(results)),
+        ("chunks".to_owned(), json!(chunks)),
+        ("summary".to_owned(), json!(summary)),
+    ]));
+
+    Ok(Some(message))
 }
 
 async fn database_query(message: Message) -> MessageResult {
@@ -276,19 +335,68 @@ async fn database_query(message: Message) -> MessageResult {
                 "all_territories" => {
                     DATABASE.command_all_territories(arguments).await
                 }
+                "clan_info" => DATABASE.command_clan_info(arguments).await,
+                "delete_territory" => {
+                    DATABASE.command_delete_territory(arguments).await
+                }
+                "erase_player" => DATABASE.command_erase_player(arguments).await,
+                "export_player" => DATABASE.command_export_player(arguments).await,
+                "find_player" => DATABASE.command_find_player(arguments).await,
+                "integrity_check" => {
+                    DATABASE.command_integrity_check(arguments).await
+                }
+                "integrity_cleanup" => {
+                    DATABASE.command_integrity_cleanup(arguments).await
+                }
+                "leaderboard" => DATABASE.command_leaderboard(arguments).await,
                 "me" => DATABASE.command_me(arguments).await,
+                "player_clan" => DATABASE.command_player_clan(arguments).await,
                 "player_info" => DATABASE.command_player_info(arguments).await,
+                "player_sessions" => {
+                    DATABASE.command_player_sessions(arguments).await
+                }
                 "player_territories" => {
                     DATABASE.command_player_territories(arguments).await
                 }
+                "player_vehicles" => {
+                    DATABASE.command_player_vehicles(arguments).await
+                }
+                "remove_clan_member" => {
+                    DATABASE.command_remove_clan_member(arguments).await
+                }
                 "reset_all" => DATABASE.command_reset_all(arguments).await,
                 "reset_player" => DATABASE.command_reset_player(arguments).await,
                 "restore" => DATABASE.command_restore(arguments).await,
                 "reward_territories" => {
                     DATABASE.command_reward_territories(arguments).await
                 }
+                "set_clan_leader" => {
+                    DATABASE.command_set_clan_leader(arguments).await
+                }
                 "set_id" => DATABASE.command_set_id(arguments).await,
+                "set_xm8_preferences" => {
+                    DATABASE.command_set_xm8_preferences(arguments).await
+                }
+                "status" => DATABASE.status(),
+                "territories_due" => {
+                    DATABASE.command_territories_due(arguments).await
+                }
                 "territory_info" => DATABASE.command_territory_info(arguments).await,
+                "territory_vehicles" => {
+                    DATABASE.command_territory_vehicles(arguments).await
+                }
+                "transfer_territory" => {
+                    DATABASE.command_transfer_territory(arguments).await
+                }
+                "transfer_vehicle" => {
+                    DATABASE.command_transfer_vehicle(arguments).await
+                }
+                "xm8_notification_counts" => {
+                    DATABASE.command_xm8_notification_counts(arguments).await
+                }
+                "xm8_preferences" => {
+                    DATABASE.command_xm8_preferences(arguments).await
+                }
                 _ => Err(QueryError::System(format!(
                     "Unexpected query \"{}\" with arguments {:?}",
                     name, arguments
@@ -16,6 +16,7 @@ use std::sync::atomic::AtomicI64;
 use std::sync::Mutex as SyncMutex;
 use std::time::Duration;
 use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::Notify;
 use tokio::time::sleep;
 
 const RECONNECT_MIN: Duration = Duration::from_secs(5); // 5 seconds
@@ -41,6 +42,7 @@ lazy_static! {
     static ref LISTENER_TASK: Arc<SyncMutex<Option<NodeTask>>> =
         Arc::new(SyncMutex::new(None));
     static ref RECONNECTION_COUNT: AtomicI64 = AtomicI64::new(0);
+    static ref XM8_WAKEUP: Notify = Notify::new();
 }
 
 pub async fn initialize(receiver: UnboundedReceiver<BotRequest>) {
@@ -58,6 +60,19 @@ pub async fn initialize(receiver: UnboundedReceiver<BotRequest>) {
     routing_thread(receiver).await;
     listener_thread(listener);
     xm8_notification_thread().await;
+    territory_reminder_thread().await;
+    xm8_purge_thread().await;
+}
+
+/// The number of days a territory can go without payment, as provided by the server on init
+pub fn territory_lifetime() -> usize {
+    lock!(INIT).territory_lifetime.parse().unwrap_or_default()
+}
+
+/// Delivers XM8 notifications now instead of on the next poll. If the thread is already
+/// delivering, it checks again as soon as it finishes
+pub fn wake_xm8_notification_thread() {
+    XM8_WAKEUP.notify_one();
 }
 
 async fn routing_thread(mut receiver: UnboundedReceiver<BotRequest>) {
@@ -138,19 +153,28 @@ fn listener_thread(listener: NodeListener<()>) {
 
 async fn xm8_notification_thread() {
     tokio::spawn(async move {
-        let time_to_wait = if cfg!(feature = "development") {
-            1.0
-        } else {
-            5.0
-        };
+        let time_to_wait =
+            Duration::from_millis(crate::CONFIG.xm8_notification_poll_interval_ms);
 
         loop {
-            sleep(Duration::from_secs_f64(time_to_wait)).await;
+            tokio::select! {
+                _ = sleep(time_to_wait) => {}
+                _ = XM8_WAKEUP.notified() => {}
+            }
 
             if !crate::READY.load(Ordering::SeqCst) {
                 continue;
             }
 
+            // Pending notifications that were never acknowledged are given up on
+            match DATABASE.fail_exhausted_xm8_notifications().await {
+                Ok(0) => {}
+                Ok(count) => warn!(
+                    "[xm8_notification_thread] ⚠ {count} notifications failed after exhausting their delivery attempts"
+                ),
+                Err(e) => error!("[xm8_notification_thread] ❌ {e}"),
+            }
+
             let notifications = match DATABASE.get_xm8_notifications().await {
                 Ok(n) => n,
                 Err(e) => {
@@ -163,7 +187,7 @@ async fn xm8_notification_thread() {
                 continue;
             }
 
-            // Update the attempt counter
+            // Moves the notifications to pending and starts their backoff
             let notification_ids: Vec<&String> =
                 notifications.iter().flat_map(|n| &n.uuids).collect();
 
@@ -192,6 +216,69 @@ async fn xm8_notification_thread() {
     });
 }
 
+async fn territory_reminder_thread() {
+    let lead_hours = crate::CONFIG.territory_reminder_lead_hours.clone();
+    if lead_hours.is_empty() {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let time_to_wait = if cfg!(feature = "development") {
+            10
+        } else {
+            300
+        };
+
+        loop {
+            sleep(Duration::from_secs(time_to_wait)).await;
+
+            if !crate::READY.load(Ordering::SeqCst) {
+                continue;
+            }
+
+            match DATABASE.queue_territory_reminders(&lead_hours).await {
+                Ok(0) => {}
+                Ok(count) => info!(
+                    "[territory_reminder_thread] ✅ Queued payment reminders for {count} territories"
+                ),
+                Err(e) => error!("[territory_reminder_thread] ❌ {e}"),
+            }
+        }
+    });
+}
+
+async fn xm8_purge_thread() {
+    let retention_days = crate::CONFIG.xm8_notification_retention_days.clone();
+    if retention_days.values().all(|days| *days == 0) {
+        return;
+    }
+
+    tokio::spawn(async move {
+        loop {
+            sleep(Duration::from_secs(3600)).await;
+
+            if !crate::READY.load(Ordering::SeqCst) {
+                continue;
+            }
+
+            match DATABASE.purge_xm8_notifications(&retention_days).await {
+                Ok(purged) => {
+                    let summary = purged
+                        .iter()
+                        .map(|(state, count)| format!("{count} {state}"))
+                        .collect::<Vec<String>>()
+                        .join(", ");
+
+                    info!(
+                        "[xm8_purge_thread] ✅ Purged XM8 notifications: {summary}"
+                    );
+                }
+                Err(e) => error!("[xm8_purge_thread] ❌ {e}"),
+            }
+        }
+    });
+}
+
 fn send_message(message: Message) -> ESMResult {
     info!(
         "[send_message] {} - outbound message - {} bytes - data size: {}, metadata size: {}",
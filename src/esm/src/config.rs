@@ -27,6 +27,15 @@ pub struct Config {
     #[serde(default = "default_database_uri")]
     pub database_uri: String,
 
+    #[serde(default = "default_database_ssl_mode")]
+    pub database_ssl_mode: String,
+
+    #[serde(default = "default_database_ssl_ca_path")]
+    pub database_ssl_ca_path: String,
+
+    #[serde(default = "default_database_socket")]
+    pub database_socket: String,
+
     #[serde(default = "default_server_mod_name")]
     pub server_mod_name: String,
 
@@ -38,6 +47,75 @@ pub struct Config {
 
     #[serde(default = "default_additional_logs")]
     pub additional_logs: Vec<String>,
+
+    #[serde(default = "default_log_search_max_results")]
+    pub log_search_max_results: usize,
+
+    #[serde(default = "default_log_search_max_results_per_file")]
+    pub log_search_max_results_per_file: usize,
+
+    #[serde(default = "default_log_search_max_context_lines")]
+    pub log_search_max_context_lines: usize,
+
+    #[serde(default = "default_log_search_chunk_size")]
+    pub log_search_chunk_size: usize,
+
+    #[serde(default = "default_slow_query_threshold_ms")]
+    pub slow_query_threshold_ms: u64,
+
+    #[serde(default = "default_query_cache_ttl_seconds")]
+    pub query_cache_ttl_seconds: HashMap<String, u64>,
+
+    #[serde(default = "default_territory_reminder_lead_hours")]
+    pub territory_reminder_lead_hours: Vec<u64>,
+
+    #[serde(default = "default_xm8_notification_retention_days")]
+    pub xm8_notification_retention_days: HashMap<String, u64>,
+
+    #[serde(default = "default_xm8_notification_dedup_seconds")]
+    pub xm8_notification_dedup_seconds: HashMap<String, u64>,
+
+    #[serde(default = "default_xm8_notification_rate_limit")]
+    pub xm8_notification_rate_limit: u64,
+
+    #[serde(default = "default_xm8_notification_rate_limit_seconds")]
+    pub xm8_notification_rate_limit_seconds: u64,
+
+    #[serde(default = "default_xm8_notification_poll_interval_ms")]
+    pub xm8_notification_poll_interval_ms: u64,
+
+    #[serde(default = "default_xm8_notification_batch_size")]
+    pub xm8_notification_batch_size: u64,
+
+    #[serde(default = "default_xm8_notification_max_attempts")]
+    pub xm8_notification_max_attempts: u64,
+
+    #[serde(default = "default_xm8_notification_retry_delay_seconds")]
+    pub xm8_notification_retry_delay_seconds: u64,
+
+    #[serde(default = "default_xm8_notification_max_retry_delay_seconds")]
+    pub xm8_notification_max_retry_delay_seconds: u64,
+
+    #[serde(default = "default_custom_territory_id_min_length")]
+    pub custom_territory_id_min_length: usize,
+
+    #[serde(default = "default_custom_territory_id_max_length")]
+    pub custom_territory_id_max_length: usize,
+
+    #[serde(default = "default_custom_territory_id_allowed_characters")]
+    pub custom_territory_id_allowed_characters: String,
+
+    #[serde(default = "default_custom_territory_id_reserved_words")]
+    pub custom_territory_id_reserved_words: Vec<String>,
+
+    #[serde(default = "default_custom_territory_id_blocklist")]
+    pub custom_territory_id_blocklist: Vec<String>,
+
+    #[serde(default = "default_custom_territory_id_cooldown_hours")]
+    pub custom_territory_id_cooldown_hours: u64,
+
+    #[serde(default = "default_custom_territory_id_redirect_days")]
+    pub custom_territory_id_redirect_days: u64,
 }
 
 impl Default for Config {
@@ -51,10 +129,46 @@ impl Default for Config {
             extdb_version: default_extdb_version(),
             log_output: default_log_output(),
             database_uri: default_database_uri(),
+            database_ssl_mode: default_database_ssl_mode(),
+            database_ssl_ca_path: default_database_ssl_ca_path(),
+            database_socket: default_database_socket(),
             server_mod_name: default_server_mod_name(),
             number_locale: default_number_locale(),
             exile_logs_search_days: default_exile_logs_search_days(),
             additional_logs: default_additional_logs(),
+            log_search_max_results: default_log_search_max_results(),
+            log_search_max_results_per_file: default_log_search_max_results_per_file(
+            ),
+            log_search_max_context_lines: default_log_search_max_context_lines(),
+            log_search_chunk_size: default_log_search_chunk_size(),
+            slow_query_threshold_ms: default_slow_query_threshold_ms(),
+            query_cache_ttl_seconds: default_query_cache_ttl_seconds(),
+            territory_reminder_lead_hours: default_territory_reminder_lead_hours(),
+            xm8_notification_retention_days: default_xm8_notification_retention_days(
+            ),
+            xm8_notification_dedup_seconds: default_xm8_notification_dedup_seconds(),
+            xm8_notification_rate_limit: default_xm8_notification_rate_limit(),
+            xm8_notification_rate_limit_seconds:
+                default_xm8_notification_rate_limit_seconds(),
+            xm8_notification_poll_interval_ms:
+                default_xm8_notification_poll_interval_ms(),
+            xm8_notification_batch_size: default_xm8_notification_batch_size(),
+            xm8_notification_max_attempts: default_xm8_notification_max_attempts(),
+            xm8_notification_retry_delay_seconds:
+                default_xm8_notification_retry_delay_seconds(),
+            xm8_notification_max_retry_delay_seconds:
+                default_xm8_notification_max_retry_delay_seconds(),
+            custom_territory_id_min_length: default_custom_territory_id_min_length(),
+            custom_territory_id_max_length: default_custom_territory_id_max_length(),
+            custom_territory_id_allowed_characters:
+                default_custom_territory_id_allowed_characters(),
+            custom_territory_id_reserved_words:
+                default_custom_territory_id_reserved_words(),
+            custom_territory_id_blocklist: default_custom_territory_id_blocklist(),
+            custom_territory_id_cooldown_hours:
+                default_custom_territory_id_cooldown_hours(),
+            custom_territory_id_redirect_days:
+                default_custom_territory_id_redirect_days(),
         }
     }
 }
@@ -99,6 +213,19 @@ fn default_database_uri() -> String {
     String::default()
 }
 
+// Empty values defer to the extDB conf file
+fn default_database_ssl_mode() -> String {
+    String::default()
+}
+
+fn default_database_ssl_ca_path() -> String {
+    String::default()
+}
+
+fn default_database_socket() -> String {
+    String::default()
+}
+
 fn default_server_mod_name() -> String {
     if cfg!(windows) {
         "@ExileServer".into()
@@ -119,6 +246,137 @@ fn default_additional_logs() -> Vec<String> {
     Vec::new()
 }
 
+// The most results a single search can return, across every file
+fn default_log_search_max_results() -> usize {
+    500
+}
+
+fn default_log_search_max_results_per_file() -> usize {
+    100
+}
+
+// The most lines a search can request before and after each match
+fn default_log_search_max_context_lines() -> usize {
+    5
+}
+
+// Results are sent to the bot in messages of this many results
+fn default_log_search_chunk_size() -> usize {
+    50
+}
+
+// Zero disables the slow query log
+fn default_slow_query_threshold_ms() -> u64 {
+    1000
+}
+
+// Caching is opt-in per query. For example, `command_all_territories: 30`
+fn default_query_cache_ttl_seconds() -> HashMap<String, u64> {
+    HashMap::new()
+}
+
+// Reminders are opt-in. For example, `[72, 24]` reminds three days and one day out
+fn default_territory_reminder_lead_hours() -> Vec<u64> {
+    vec![]
+}
+
+// Days to keep delivered notifications in each state. States that aren't listed, or are set to
+// zero, are kept forever
+fn default_xm8_notification_retention_days() -> HashMap<String, u64> {
+    HashMap::from([("sent".into(), 7), ("failed".into(), 30)])
+}
+
+// Seconds a notification type is ignored for after it was sent to the same player about the
+// same territory. Types that aren't listed, or are set to zero, are never ignored
+fn default_xm8_notification_dedup_seconds() -> HashMap<String, u64> {
+    HashMap::from([
+        ("hack-started".into(), 300),
+        ("grind-started".into(), 300),
+        ("charge-plant-started".into(), 300),
+        ("flag-steal-started".into(), 300),
+    ])
+}
+
+// The most notifications a player receives about the same territory within
+// `xm8_notification_rate_limit_seconds`. Zero disables the rate limit
+fn default_xm8_notification_rate_limit() -> u64 {
+    10
+}
+
+fn default_xm8_notification_rate_limit_seconds() -> u64 {
+    300
+}
+
+// How often undelivered notifications are checked for. New notifications are delivered right
+// away regardless
+fn default_xm8_notification_poll_interval_ms() -> u64 {
+    if cfg!(feature = "development") {
+        1000
+    } else {
+        5000
+    }
+}
+
+// The most notifications sent to the bot at once
+fn default_xm8_notification_batch_size() -> u64 {
+    100
+}
+
+// Notifications that haven't been acknowledged after this many attempts are marked failed
+fn default_xm8_notification_max_attempts() -> u64 {
+    10
+}
+
+// Delivery is retried with exponential backoff. The first retry waits this long and each one
+// after that waits twice as long as the last, up to `xm8_notification_max_retry_delay_seconds`
+fn default_xm8_notification_retry_delay_seconds() -> u64 {
+    30
+}
+
+fn default_xm8_notification_max_retry_delay_seconds() -> u64 {
+    3600
+}
+
+fn default_custom_territory_id_min_length() -> usize {
+    3
+}
+
+// The database column holds up to 100 characters
+fn default_custom_territory_id_max_length() -> usize {
+    20
+}
+
+// Checked case-insensitively, so uppercase letters are allowed too
+fn default_custom_territory_id_allowed_characters() -> String {
+    "abcdefghijklmnopqrstuvwxyz0123456789-_".into()
+}
+
+// Custom IDs that can't be used. Compared case-insensitively against the whole ID
+fn default_custom_territory_id_reserved_words() -> Vec<String> {
+    vec![
+        "admin".into(),
+        "esm".into(),
+        "exile".into(),
+        "server".into(),
+    ]
+}
+
+// Words that can't appear anywhere in a custom ID. Compared case-insensitively
+fn default_custom_territory_id_blocklist() -> Vec<String> {
+    vec![]
+}
+
+// How long a territory owner has to wait between changes. Territory admins aren't limited
+fn default_custom_territory_id_cooldown_hours() -> u64 {
+    24
+}
+
+// How long a previous custom ID keeps pointing to its territory. Until then, no other
+// territory can claim it
+fn default_custom_territory_id_redirect_days() -> u64 {
+    30
+}
+
 impl std::fmt::Display for Config {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:#?}", self)
@@ -148,7 +406,17 @@ impl Config {
 
     pub fn validate(&self) -> ConfigResult {
         self.validate_connection_url()?;
-        self.validate_number_locale()
+        self.validate_number_locale()?;
+        self.validate_database_ssl_mode()?;
+        self.validate_database_uri()?;
+        self.validate_log_search()?;
+        self.validate_query_cache_ttl_seconds()?;
+        self.validate_territory_reminder_lead_hours()?;
+        self.validate_xm8_notification_retention_days()?;
+        self.validate_xm8_notification_dedup_seconds()?;
+        self.validate_xm8_notification_rate_limit()?;
+        self.validate_xm8_notification_delivery()?;
+        self.validate_custom_territory_id()
     }
 
     fn validate_connection_url(&self) -> ConfigResult {
@@ -167,6 +435,195 @@ impl Config {
         }
     }
 
+    fn validate_database_ssl_mode(&self) -> ConfigResult {
+        match self.database_ssl_mode.parse::<crate::database::SslMode>() {
+            Ok(_) => Ok(()),
+            Err(e) => Err(format!(
+                "Failed to validate database_ssl_mode -> {:?}. Reason: {}",
+                self.database_ssl_mode, e
+            )),
+        }
+    }
+
+    // The URI is used as is. These would otherwise be ignored without telling anyone
+    fn validate_database_uri(&self) -> ConfigResult {
+        if self.database_uri.is_empty() {
+            return Ok(());
+        }
+
+        let ignored: Vec<&str> = [
+            ("database_ssl_mode", &self.database_ssl_mode),
+            ("database_socket", &self.database_socket),
+        ]
+        .iter()
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(name, _)| *name)
+        .collect();
+
+        if ignored.is_empty() {
+            return Ok(());
+        }
+
+        Err(format!(
+            "{} can't be used with database_uri. Add them to the URI instead, for example \"?require_ssl=true&socket=/var/run/mysqld/mysqld.sock\"",
+            ignored.join(" and ")
+        ))
+    }
+
+    fn validate_log_search(&self) -> ConfigResult {
+        let zero = [
+            ("log_search_max_results", self.log_search_max_results),
+            (
+                "log_search_max_results_per_file",
+                self.log_search_max_results_per_file,
+            ),
+            ("log_search_chunk_size", self.log_search_chunk_size),
+        ]
+        .iter()
+        .find(|(_, value)| *value == 0)
+        .copied();
+
+        match zero {
+            Some((name, value)) => Err(format!(
+                "Failed to validate {name} -> {value:?}. Reason: Must be greater than zero"
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn validate_query_cache_ttl_seconds(&self) -> ConfigResult {
+        let cacheable = crate::database::CACHEABLE_QUERIES;
+
+        match self
+            .query_cache_ttl_seconds
+            .keys()
+            .find(|name| !cacheable.contains(&name.as_str()))
+        {
+            Some(name) => Err(format!(
+                "Failed to validate query_cache_ttl_seconds -> {:?}. Reason: Only {} can be cached",
+                name,
+                cacheable.join(", ")
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn validate_territory_reminder_lead_hours(&self) -> ConfigResult {
+        if self.territory_reminder_lead_hours.contains(&0) {
+            return Err(format!(
+                "Failed to validate territory_reminder_lead_hours -> {:?}. Reason: Lead times must be greater than zero",
+                self.territory_reminder_lead_hours
+            ));
+        }
+
+        Ok(())
+    }
+
+    // New and pending notifications haven't been delivered yet. Purging them would drop
+    // them without anyone finding out
+    fn validate_xm8_notification_retention_days(&self) -> ConfigResult {
+        let states = ["sent", "failed"];
+
+        match self
+            .xm8_notification_retention_days
+            .keys()
+            .find(|state| !states.contains(&state.as_str()))
+        {
+            Some(state) => Err(format!(
+                "Failed to validate xm8_notification_retention_days -> {:?}. Reason: Expected one of: {}",
+                state,
+                states.join(", ")
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn validate_xm8_notification_dedup_seconds(&self) -> ConfigResult {
+        match self
+            .xm8_notification_dedup_seconds
+            .keys()
+            .find_map(|name| name.parse::<NotificationType>().err())
+        {
+            Some(e) => Err(format!(
+                "Failed to validate xm8_notification_dedup_seconds. Reason: {e}"
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn validate_xm8_notification_rate_limit(&self) -> ConfigResult {
+        if self.xm8_notification_rate_limit > 0
+            && self.xm8_notification_rate_limit_seconds == 0
+        {
+            return Err(format!(
+                "Failed to validate xm8_notification_rate_limit_seconds -> {:?}. Reason: Must be greater than zero when xm8_notification_rate_limit is set",
+                self.xm8_notification_rate_limit_seconds
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn validate_xm8_notification_delivery(&self) -> ConfigResult {
+        let zero = [
+            (
+                "xm8_notification_poll_interval_ms",
+                self.xm8_notification_poll_interval_ms,
+            ),
+            (
+                "xm8_notification_batch_size",
+                self.xm8_notification_batch_size,
+            ),
+            (
+                "xm8_notification_max_attempts",
+                self.xm8_notification_max_attempts,
+            ),
+            (
+                "xm8_notification_retry_delay_seconds",
+                self.xm8_notification_retry_delay_seconds,
+            ),
+        ]
+        .iter()
+        .find(|(_, value)| *value == 0)
+        .copied();
+
+        if let Some((name, value)) = zero {
+            return Err(format!(
+                "Failed to validate {name} -> {value:?}. Reason: Must be greater than zero"
+            ));
+        }
+
+        if self.xm8_notification_max_retry_delay_seconds
+            < self.xm8_notification_retry_delay_seconds
+        {
+            return Err(format!(
+                "Failed to validate xm8_notification_max_retry_delay_seconds -> {:?}. Reason: Must be at least xm8_notification_retry_delay_seconds",
+                self.xm8_notification_max_retry_delay_seconds
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn validate_custom_territory_id(&self) -> ConfigResult {
+        let (min, max) = (
+            self.custom_territory_id_min_length,
+            self.custom_territory_id_max_length,
+        );
+
+        if min == 0 || max > 100 || min > max {
+            return Err(format!(
+                "Failed to validate custom_territory_id_min_length and custom_territory_id_max_length -> {min:?}, {max:?}. Reason: Lengths must be between 1 and 100, with the minimum no larger than the maximum"
+            ));
+        }
+
+        if self.custom_territory_id_allowed_characters.is_empty() {
+            return Err("Failed to validate custom_territory_id_allowed_characters. Reason: At least one character must be allowed".into());
+        }
+
+        Ok(())
+    }
+
     fn validate_number_locale(&self) -> ConfigResult {
         match Locale::from_name(&self.number_locale) {
             Ok(_) => Ok(()),
@@ -177,3 +634,29 @@ impl Config {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_only_purges_delivered_notifications() {
+        let config = |state: &str| Config {
+            xm8_notification_retention_days: HashMap::from([(state.to_string(), 7)]),
+            ..Config::default()
+        };
+
+        assert!(config("sent").validate_xm8_notification_retention_days().is_ok());
+        assert!(config("failed").validate_xm8_notification_retention_days().is_ok());
+
+        for state in ["new", "pending", "delivered"] {
+            assert!(
+                config(state)
+                    .validate_xm8_notification_retention_days()
+                    .is_err(),
+                "{}",
+                state
+            );
+        }
+    }
+}
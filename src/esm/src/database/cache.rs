@@ -0,0 +1,284 @@
+use super::*;
+
+use parking_lot::Mutex as ParkingMutex;
+
+/// Read-only queries whose results can be cached. A query is only cached if it has a TTL in
+/// `query_cache_ttl_seconds`
+pub const CACHEABLE_QUERIES: &[&str] = &[
+    "command_all_territories",
+    // Kills, deaths, and score change in game without going through ESM. Only resets and
+    // erasures invalidate the rankings, so they rely on a short TTL otherwise
+    "command_leaderboard",
+    "command_player_info",
+    "command_territory_info",
+];
+
+/// What a cached result contains, so mutations can invalidate only the results they affect
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CacheTag {
+    /// The list of every territory
+    Territories,
+
+    /// A territory's database ID
+    Territory(u64),
+
+    /// A player's Steam UID
+    Player(String),
+
+    /// Any player ranking
+    Leaderboard,
+}
+
+#[derive(Clone, Default)]
+pub struct QueryCache {
+    entries: Arc<ParkingMutex<HashMap<String, CacheEntry>>>,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    results: Vec<String>,
+    tags: Vec<CacheTag>,
+    expires_at: Instant,
+}
+
+impl QueryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds the key for a query and its arguments. The arguments are sorted so the order they
+    /// were provided in does not matter
+    pub fn key(name: &str, arguments: &HashMap<String, String>) -> String {
+        let mut arguments: Vec<(&String, &String)> = arguments.iter().collect();
+        arguments.sort();
+
+        let arguments = arguments
+            .into_iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<String>>()
+            .join("&");
+
+        format!("{name}?{arguments}")
+    }
+
+    /// Returns how long the query's results should be cached for, if at all
+    pub fn ttl(name: &str) -> Option<Duration> {
+        if !CACHEABLE_QUERIES.contains(&name) {
+            return None;
+        }
+
+        match crate::CONFIG.query_cache_ttl_seconds.get(name) {
+            Some(seconds) if *seconds > 0 => Some(Duration::from_secs(*seconds)),
+            _ => None,
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<Vec<String>> {
+        let mut entries = self.entries.lock();
+
+        match entries.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => {
+                Some(entry.results.to_owned())
+            }
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub fn insert(
+        &self,
+        key: String,
+        results: Vec<String>,
+        tags: Vec<CacheTag>,
+        ttl: Duration,
+    ) {
+        let mut entries = self.entries.lock();
+
+        // Expired entries are only removed when they are read. Clean them up as new ones come in
+        // so the cache doesn't grow forever
+        let now = Instant::now();
+        entries.retain(|_, entry| entry.expires_at > now);
+
+        entries.insert(
+            key,
+            CacheEntry {
+                results,
+                tags,
+                expires_at: now + ttl,
+            },
+        );
+    }
+
+    /// Removes every cached result that contains any of the tags
+    pub fn invalidate(&self, tags: &[CacheTag]) {
+        if tags.is_empty() {
+            return;
+        }
+
+        self.entries
+            .lock()
+            .retain(|_, entry| !entry.tags.iter().any(|tag| tags.contains(tag)));
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+}
+
+impl CacheTag {
+    /// Tags the results by the territories and players they contain. Territory IDs are the
+    /// encoded IDs returned to the bot, so they are decoded back to their database IDs
+    pub fn from_results(hasher: &Hasher, results: &[String]) -> Vec<CacheTag> {
+        let mut tags = vec![];
+
+        for result in results {
+            let Ok(value) = serde_json::from_str::<JSONValue>(result) else {
+                continue;
+            };
+
+            Self::collect(hasher, &value, &mut tags);
+
+            if let Some(territories) =
+                value.get("territories").and_then(|t| t.as_array())
+            {
+                for territory in territories {
+                    Self::collect(hasher, territory, &mut tags);
+                }
+            }
+        }
+
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    fn collect(hasher: &Hasher, value: &JSONValue, tags: &mut Vec<CacheTag>) {
+        if let Some(id) = value
+            .get("id")
+            .and_then(|id| id.as_str())
+            .and_then(|id| hasher.decode(id))
+        {
+            tags.push(CacheTag::Territory(id));
+        }
+
+        for key in ["uid", "owner_uid"] {
+            if let Some(uid) = value.get(key).and_then(|uid| uid.as_str()) {
+                tags.push(CacheTag::Player(uid.to_owned()));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_ignores_argument_order() {
+        let first = HashMap::from([
+            ("a".to_string(), "1".to_string()),
+            ("b".to_string(), "2".to_string()),
+        ]);
+
+        let mut second = HashMap::new();
+        second.insert("b".to_string(), "2".to_string());
+        second.insert("a".to_string(), "1".to_string());
+
+        assert_eq!(
+            QueryCache::key("command_me", &first),
+            QueryCache::key("command_me", &second)
+        );
+
+        assert_eq!(QueryCache::key("command_me", &first), "command_me?a=1&b=2");
+    }
+
+    #[test]
+    fn it_expires_entries() {
+        let cache = QueryCache::new();
+
+        cache.insert("a".into(), vec!["1".into()], vec![], Duration::ZERO);
+        cache.insert(
+            "b".into(),
+            vec!["2".into()],
+            vec![],
+            Duration::from_secs(60),
+        );
+
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.get("b"), Some(vec!["2".to_string()]));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn it_invalidates_by_tag() {
+        let cache = QueryCache::new();
+        let ttl = Duration::from_secs(60);
+
+        cache.insert(
+            "territory".into(),
+            vec![],
+            vec![CacheTag::Territory(1), CacheTag::Player("1".into())],
+            ttl,
+        );
+
+        cache.insert(
+            "player".into(),
+            vec![],
+            vec![CacheTag::Player("2".into())],
+            ttl,
+        );
+        cache.insert("all".into(), vec![], vec![CacheTag::Territories], ttl);
+
+        cache.invalidate(&[CacheTag::Territory(1)]);
+
+        assert_eq!(cache.get("territory"), None);
+        assert!(cache.get("player").is_some());
+        assert!(cache.get("all").is_some());
+
+        cache.invalidate(&[CacheTag::Player("2".into()), CacheTag::Territories]);
+
+        assert_eq!(cache.len(), 0);
+    }
+
+    #[test]
+    fn it_tags_results() {
+        let hasher = Hasher::new();
+        hasher.set_salt("cache");
+
+        let territory = json!({
+            "id": hasher.encode("5"),
+            "owner_uid": "76561198037177305",
+        });
+
+        let player = json!({
+            "uid": "76561198025434405",
+            "territories": [{ "id": hasher.encode("6"), "name": "Home" }]
+        });
+
+        let tags = CacheTag::from_results(
+            &hasher,
+            &[territory.to_string(), player.to_string(), territory.to_string()],
+        );
+
+        assert_eq!(
+            tags,
+            vec![
+                CacheTag::Territory(5),
+                CacheTag::Territory(6),
+                CacheTag::Player("76561198025434405".into()),
+                CacheTag::Player("76561198037177305".into()),
+            ]
+        );
+    }
+}
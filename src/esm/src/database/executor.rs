@@ -0,0 +1,159 @@
+use super::*;
+
+use mysql_async::prelude::FromRow;
+use mysql_async::{from_row, Row, Transaction};
+use std::pin::Pin;
+
+pub type ExecutorFuture<'a, T> =
+    Pin<Box<dyn Future<Output = SQLResult<T>> + Send + 'a>>;
+
+/// Anything that can execute a statement. The query files only talk to this trait so they
+/// can run against a MySQL connection, a transaction, or a fake database in the tests
+pub trait Executor: Send {
+    /// Executes the statement and returns the rows from the first result set
+    fn fetch<'a>(
+        &'a mut self,
+        statement: &'a str,
+        params: Params,
+    ) -> ExecutorFuture<'a, Vec<Row>>;
+
+    /// Executes the statement and returns the number of affected rows
+    fn execute<'a>(
+        &'a mut self,
+        statement: &'a str,
+        params: Params,
+    ) -> ExecutorFuture<'a, u64>;
+
+    /// Executes the statement once for every set of params
+    fn execute_batch<'a>(
+        &'a mut self,
+        statement: &'a str,
+        params: Vec<Params>,
+    ) -> ExecutorFuture<'a, ()>;
+}
+
+impl Executor for Conn {
+    fn fetch<'a>(
+        &'a mut self,
+        statement: &'a str,
+        params: Params,
+    ) -> ExecutorFuture<'a, Vec<Row>> {
+        Queryable::exec(self, statement, params)
+    }
+
+    fn execute<'a>(
+        &'a mut self,
+        statement: &'a str,
+        params: Params,
+    ) -> ExecutorFuture<'a, u64> {
+        Box::pin(async move {
+            Queryable::exec_drop(&mut *self, statement, params).await?;
+            Ok(self.affected_rows())
+        })
+    }
+
+    fn execute_batch<'a>(
+        &'a mut self,
+        statement: &'a str,
+        params: Vec<Params>,
+    ) -> ExecutorFuture<'a, ()> {
+        Queryable::exec_batch(self, statement, params)
+    }
+}
+
+impl Executor for Transaction<'_> {
+    fn fetch<'a>(
+        &'a mut self,
+        statement: &'a str,
+        params: Params,
+    ) -> ExecutorFuture<'a, Vec<Row>> {
+        Queryable::exec(self, statement, params)
+    }
+
+    fn execute<'a>(
+        &'a mut self,
+        statement: &'a str,
+        params: Params,
+    ) -> ExecutorFuture<'a, u64> {
+        Box::pin(async move {
+            Queryable::exec_drop(&mut *self, statement, params).await?;
+            Ok(self.affected_rows())
+        })
+    }
+
+    fn execute_batch<'a>(
+        &'a mut self,
+        statement: &'a str,
+        params: Vec<Params>,
+    ) -> ExecutorFuture<'a, ()> {
+        Queryable::exec_batch(self, statement, params)
+    }
+}
+
+// These mirror mysql_async's Queryable so the query files read the same regardless of what is
+// executing them
+impl dyn Executor + '_ {
+    pub async fn exec_map<T, P, F, U>(
+        &mut self,
+        statement: &str,
+        params: P,
+        mapper: F,
+    ) -> SQLResult<Vec<U>>
+    where
+        T: FromRow,
+        P: Into<Params>,
+        F: FnMut(T) -> U,
+    {
+        let rows = self.fetch(statement, params.into()).await?;
+        Ok(rows.into_iter().map(from_row).map(mapper).collect())
+    }
+
+    pub async fn exec_first<T, P>(
+        &mut self,
+        statement: &str,
+        params: P,
+    ) -> SQLResult<Option<T>>
+    where
+        T: FromRow,
+        P: Into<Params>,
+    {
+        let rows = self.fetch(statement, params.into()).await?;
+        Ok(rows.into_iter().next().map(from_row))
+    }
+
+    pub async fn exec_drop<P>(&mut self, statement: &str, params: P) -> SQLResult<()>
+    where
+        P: Into<Params>,
+    {
+        self.execute(statement, params.into()).await.map(|_| ())
+    }
+
+    pub async fn exec_batch<P, I>(
+        &mut self,
+        statement: &str,
+        params: I,
+    ) -> SQLResult<()>
+    where
+        P: Into<Params>,
+        I: IntoIterator<Item = P>,
+    {
+        let params: Vec<Params> = params.into_iter().map(Into::into).collect();
+        self.execute_batch(statement, params).await
+    }
+
+    pub async fn query_map<T, F, U>(
+        &mut self,
+        statement: &str,
+        mapper: F,
+    ) -> SQLResult<Vec<U>>
+    where
+        T: FromRow,
+        F: FnMut(T) -> U,
+    {
+        self.exec_map(statement, Params::Empty, mapper).await
+    }
+
+    pub async fn query_drop(&mut self, statement: &str) -> SQLResult<()> {
+        self.exec_drop(statement, Params::Empty).await
+    }
+}
@@ -0,0 +1,383 @@
+use super::*;
+
+use mysql_async::consts::ColumnType;
+use mysql_async::{Column, Row, Value};
+use mysql_common::row::new_row;
+use regex::Regex;
+use std::path::PathBuf;
+
+/// A stand-in for MySQL. Statements return whatever rows were seeded for them. It is
+/// read-only unless a write is seeded with the number of rows it affects
+#[derive(Default)]
+pub struct FakeExecutor {
+    responses: HashMap<String, Vec<Row>>,
+    affected: HashMap<String, u64>,
+    pub statements: Vec<String>,
+}
+
+impl FakeExecutor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn respond_with(mut self, statement: &str, rows: Vec<Row>) -> Self {
+        self.responses.insert(normalize(statement), rows);
+        self
+    }
+
+    pub fn affect(mut self, statement: &str, rows: u64) -> Self {
+        self.affected.insert(normalize(statement), rows);
+        self
+    }
+
+    fn write(&mut self, statement: &str) -> SQLResult<u64> {
+        self.statements.push(statement.to_owned());
+
+        match self.affected.get(&normalize(statement)) {
+            Some(rows) => Ok(*rows),
+            None => Err(mysql_async::Error::Other(
+                format!("The fake database is read-only. Statement: {statement}")
+                    .into(),
+            )),
+        }
+    }
+}
+
+impl Executor for FakeExecutor {
+    fn fetch<'a>(
+        &'a mut self,
+        statement: &'a str,
+        _params: Params,
+    ) -> ExecutorFuture<'a, Vec<Row>> {
+        self.statements.push(statement.to_owned());
+
+        let result = match self.responses.get(&normalize(statement)) {
+            Some(rows) => Ok(rows.clone()),
+            None => Err(mysql_async::Error::Other(
+                format!("No rows were seeded for statement: {statement}").into(),
+            )),
+        };
+
+        Box::pin(async move { result })
+    }
+
+    fn execute<'a>(
+        &'a mut self,
+        statement: &'a str,
+        _params: Params,
+    ) -> ExecutorFuture<'a, u64> {
+        let result = self.write(statement);
+        Box::pin(async move { result })
+    }
+
+    fn execute_batch<'a>(
+        &'a mut self,
+        statement: &'a str,
+        _params: Vec<Params>,
+    ) -> ExecutorFuture<'a, ()> {
+        let result = self.write(statement).map(|_| ());
+        Box::pin(async move { result })
+    }
+}
+
+/// The Exile schema, as defined by exile.sql and the migrations in @esm/sql
+pub struct Fixture {
+    tables: HashMap<String, Vec<ColumnDefinition>>,
+}
+
+#[derive(Debug, Clone)]
+struct ColumnDefinition {
+    name: String,
+    column_type: ColumnType,
+    default: Value,
+}
+
+impl Fixture {
+    pub fn load() -> Self {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..");
+
+        let mut migrations: Vec<PathBuf> = std::fs::read_dir(root.join("@esm/sql"))
+            .expect("@esm/sql should exist")
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|e| e == "sql"))
+            .collect();
+
+        migrations.sort();
+
+        let mut fixture = Fixture {
+            tables: HashMap::new(),
+        };
+
+        for path in std::iter::once(root.join("../exile.sql")).chain(migrations) {
+            let contents = std::fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("Failed to read {:?}. {}", path, e));
+
+            fixture.apply(&contents);
+        }
+
+        fixture
+    }
+
+    /// Every Database built by this method shares the same salt so encoded IDs are predictable
+    pub fn database() -> Database {
+        let database = Database {
+            extdb_version: 3,
+            hasher: Hasher::new(),
+            metrics: Metrics::new(),
+            cache: QueryCache::new(),
+            connection_pool: Arc::new(Mutex::new(None)),
+            sql: Queries::from_directory(
+                &PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                    .join("../@esm/sql/queries"),
+            ),
+        };
+
+        database.hasher.set_salt("fixture");
+        database
+    }
+
+    pub fn columns(&self, table: &str) -> Vec<String> {
+        self.tables
+            .get(table)
+            .map(|columns| columns.iter().map(|c| c.name.to_owned()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Creates a row for the table. Columns that aren't provided use the schema's default value
+    pub fn row(&self, table: &str, values: &[(&str, Value)]) -> Row {
+        let Some(definitions) = self.tables.get(table) else {
+            panic!("{} does not exist in the schema", table);
+        };
+
+        for (name, _) in values {
+            assert!(
+                definitions.iter().any(|d| d.name == *name),
+                "{}.{} does not exist in the schema",
+                table,
+                name
+            );
+        }
+
+        let columns: Vec<Column> = definitions
+            .iter()
+            .map(|d| Column::new(d.column_type).with_name(d.name.as_bytes()))
+            .collect();
+
+        let values = definitions
+            .iter()
+            .map(|definition| {
+                values
+                    .iter()
+                    .find(|(name, _)| *name == definition.name)
+                    .map(|(_, value)| value.to_owned())
+                    .unwrap_or_else(|| definition.default.to_owned())
+            })
+            .collect();
+
+        new_row(values, columns.into())
+    }
+
+    /// Creates a row with only these columns, for statements that select from multiple tables
+    pub fn result(values: &[(&str, Value)]) -> Row {
+        let columns: Vec<Column> = values
+            .iter()
+            .map(|(name, _)| {
+                Column::new(ColumnType::MYSQL_TYPE_VAR_STRING)
+                    .with_name(name.as_bytes())
+            })
+            .collect();
+
+        let values = values.iter().map(|(_, value)| value.to_owned()).collect();
+
+        new_row(values, columns.into())
+    }
+
+    fn apply(&mut self, contents: &str) {
+        let create_table = Regex::new(
+            r"(?is)^CREATE\s+TABLE\s+(?:IF\s+NOT\s+EXISTS\s+)?`?(\w+)`?\s*\((.*)\)[^)]*$",
+        )
+        .unwrap();
+
+        let alter_table =
+            Regex::new(r"(?is)^ALTER\s+TABLE\s+`?(\w+)`?\s+(.*)$").unwrap();
+        let add_column = Regex::new(r"(?is)^ADD\s+COLUMN\s+(.*)$").unwrap();
+
+        let contents: String = contents
+            .lines()
+            .filter(|line| !line.trim_start().starts_with("--"))
+            .collect::<Vec<&str>>()
+            .join("\n");
+
+        for statement in contents.split(';').map(str::trim) {
+            if let Some(captures) = create_table.captures(statement) {
+                let columns = split_definitions(&captures[2])
+                    .iter()
+                    .filter_map(|d| parse_column(d))
+                    .collect();
+
+                self.tables.insert(captures[1].to_string(), columns);
+            } else if let Some(captures) = alter_table.captures(statement) {
+                let table = self.tables.entry(captures[1].to_string()).or_default();
+
+                for definition in split_definitions(&captures[2]) {
+                    if let Some(column) = add_column
+                        .captures(&definition)
+                        .and_then(|c| parse_column(&c[1]))
+                    {
+                        table.push(column);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// The responses are matched regardless of formatting
+fn normalize(statement: &str) -> String {
+    statement
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+// Splits on commas that aren't inside of parentheses or quotes
+fn split_definitions(body: &str) -> Vec<String> {
+    let mut definitions = vec![];
+    let mut current = String::new();
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+
+    for character in body.chars() {
+        match (character, quote) {
+            ('\'' | '"', None) => quote = Some(character),
+            (c, Some(q)) if c == q => quote = None,
+            ('(', None) => depth += 1,
+            (')', None) => depth -= 1,
+            (',', None) if depth == 0 => {
+                definitions.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+
+        current.push(character);
+    }
+
+    if !current.trim().is_empty() {
+        definitions.push(current.trim().to_string());
+    }
+
+    definitions
+}
+
+fn parse_column(definition: &str) -> Option<ColumnDefinition> {
+    let mut parts = definition.split_whitespace();
+    let name = parts.next()?.trim_matches('`');
+
+    let keywords = ["PRIMARY", "KEY", "INDEX", "UNIQUE", "CONSTRAINT", "FOREIGN"];
+    if keywords.contains(&name.to_ascii_uppercase().as_str()) {
+        return None;
+    }
+
+    let data_type = parts.next()?.to_ascii_lowercase();
+    let data_type = data_type.split('(').next().unwrap_or_default();
+    let uppercase = definition.to_ascii_uppercase();
+    let nullable = !uppercase.contains("NOT NULL");
+
+    let column_type = match data_type {
+        "int" | "bigint" | "tinyint" => ColumnType::MYSQL_TYPE_LONGLONG,
+        "double" | "float" => ColumnType::MYSQL_TYPE_DOUBLE,
+        "datetime" => ColumnType::MYSQL_TYPE_DATETIME,
+        _ => ColumnType::MYSQL_TYPE_VAR_STRING,
+    };
+
+    let default = Regex::new(r#"(?i)\bDEFAULT\s+('([^']*)'|"([^"]*)"|(\S+))"#)
+        .unwrap()
+        .captures(definition)
+        .map(|c| {
+            c.get(2)
+                .or_else(|| c.get(3))
+                .or_else(|| c.get(4))
+                .map(|m| m.as_str().to_string())
+                .unwrap_or_default()
+        });
+
+    let default = match default.as_deref() {
+        Some(value) if value.eq_ignore_ascii_case("NULL") => Value::NULL,
+        Some(value) if value.eq_ignore_ascii_case("CURRENT_TIMESTAMP") => {
+            Value::from(Utc::now().naive_utc())
+        }
+        Some(value) => match column_type {
+            ColumnType::MYSQL_TYPE_LONGLONG => {
+                Value::Int(value.parse().unwrap_or_default())
+            }
+            ColumnType::MYSQL_TYPE_DOUBLE => {
+                Value::Double(value.parse().unwrap_or_default())
+            }
+            _ => Value::from(value),
+        },
+        None if nullable => Value::NULL,
+        None => match column_type {
+            ColumnType::MYSQL_TYPE_LONGLONG => Value::Int(0),
+            ColumnType::MYSQL_TYPE_DOUBLE => Value::Double(0.0),
+            ColumnType::MYSQL_TYPE_DATETIME => Value::from(Utc::now().naive_utc()),
+            _ => Value::from(""),
+        },
+    };
+
+    Some(ColumnDefinition {
+        name: name.to_string(),
+        column_type,
+        default,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::queries::select_column;
+
+    #[test]
+    fn it_loads_the_schema_and_migrations() {
+        let fixture = Fixture::load();
+
+        let columns = fixture.columns("territory");
+        assert!(columns.contains(&"owner_uid".to_string()));
+        assert!(columns.contains(&"esm_custom_id".to_string()));
+        assert!(columns.contains(&"esm_payment_counter".to_string()));
+
+        let columns = fixture.columns("xm8_notification");
+        assert!(columns.contains(&"state".to_string()));
+        assert!(!columns.contains(&"INDEX".to_string()));
+    }
+
+    #[test]
+    fn it_fills_in_defaults() {
+        let fixture = Fixture::load();
+        let row =
+            fixture.row("account", &[("uid", Value::from("76561198037177305"))]);
+
+        assert_eq!(
+            select_column::<String>(&row, "uid").unwrap(),
+            "76561198037177305"
+        );
+        assert_eq!(select_column::<isize>(&row, "locker").unwrap(), 0);
+        assert_eq!(
+            select_column::<Option<NaiveDateTime>>(&row, "last_disconnect_at")
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn it_is_read_only() {
+        let mut fake = FakeExecutor::new();
+        let connection: &mut dyn Executor = &mut fake;
+
+        assert!(connection
+            .exec_drop("DELETE FROM player", Params::Empty)
+            .await
+            .is_err());
+    }
+}
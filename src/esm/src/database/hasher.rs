@@ -1,19 +1,38 @@
 use super::*;
 
 use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+/// A salt and the version that is encoded into every ID it produces. Salts for newer secrets
+/// are derived from the secret so they can be saved without exposing it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Salt {
+    pub version: u64,
+    pub salt: String,
+}
 
 #[derive(Clone)]
 pub struct Hasher {
-    builder: Arc<RwLock<harsh::Harsh>>,
+    // Newest first. The first salt encodes and every salt decodes
+    builders: Arc<RwLock<Vec<(Salt, harsh::Harsh)>>>,
+
+    // Used until a salt is set. It is never kept
+    placeholder: harsh::Harsh,
 }
 
 impl Hasher {
     const ALPHABET: &'static str = "abcdefghijklmnopqrstuvwxyz";
     const LENGTH: usize = 5;
 
+    /// IDs encoded before salts were versioned only contain the database ID and are salted with
+    /// the secret itself. That secret keeps encoding them this way so the IDs players already
+    /// have don't change until the secret does
+    const LEGACY_VERSION: u64 = 0;
+
     pub fn new() -> Self {
         Hasher {
-            builder: Arc::new(RwLock::new(Self::builder(&random_bs_go!()))),
+            builders: Arc::new(RwLock::new(vec![])),
+            placeholder: Self::builder(&random_bs_go!()),
         }
     }
 
@@ -31,18 +50,212 @@ impl Hasher {
             return String::new();
         };
 
-        self.builder.read().encode(&[id])
+        let builders = self.builders.read();
+
+        match builders.first() {
+            Some((salt, builder)) if salt.version != Hasher::LEGACY_VERSION => {
+                builder.encode(&[id, salt.version])
+            }
+            Some((_, builder)) => builder.encode(&[id]),
+            None => self.placeholder.encode(&[id]),
+        }
     }
 
+    /// Tries every salt, newest first
     pub fn decode(&self, input: &str) -> Option<u64> {
-        let Ok(numbers) = self.builder.read().decode(input) else {
-            return None;
+        let builders = self.builders.read();
+
+        if builders.is_empty() {
+            return match self.placeholder.decode(input).ok()?[..] {
+                [id] => Some(id),
+                _ => None,
+            };
+        }
+
+        builders.iter().find_map(|(salt, builder)| {
+            match (salt.version, &builder.decode(input).ok()?[..]) {
+                (Hasher::LEGACY_VERSION, [id]) => Some(*id),
+                (version, [id, encoded_version]) if *encoded_version == version => {
+                    Some(*id)
+                }
+                _ => None,
+            }
+        })
+    }
+
+    /// Encodes with the secret from now on. The first secret is used as is so existing IDs
+    /// keep working. Any other secret is encoded with a salt derived from it
+    pub fn set_secret(&self, secret: &str) {
+        let legacy = {
+            let builders = self.builders.read();
+
+            builders.is_empty()
+                || builders.iter().any(|(s, _)| {
+                    s.version == Hasher::LEGACY_VERSION && s.salt == secret
+                })
         };
 
-        numbers.get(0).copied()
+        if legacy {
+            self.promote(Salt {
+                version: Hasher::LEGACY_VERSION,
+                salt: secret.to_owned(),
+            });
+
+            return;
+        }
+
+        let salt = openssl::sha::sha256(secret.as_bytes())
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+
+        self.set_salt(&salt);
     }
 
+    /// Encodes with this salt from now on. The previous salts are kept for decoding and a
+    /// salt that has been used before keeps its version
     pub fn set_salt(&self, salt: &str) {
-        *self.builder.write() = Self::builder(salt)
+        let version = {
+            let builders = self.builders.read();
+
+            builders
+                .iter()
+                .find(|(s, _)| s.version != Hasher::LEGACY_VERSION && s.salt == salt)
+                .map(|(s, _)| s.version)
+                .unwrap_or_else(|| {
+                    builders.iter().map(|(s, _)| s.version).max().unwrap_or(0) + 1
+                })
+        };
+
+        self.promote(Salt {
+            version,
+            salt: salt.to_owned(),
+        });
+    }
+
+    fn promote(&self, salt: Salt) {
+        let mut builders = self.builders.write();
+
+        builders.retain(|(s, _)| s.version != salt.version);
+
+        let builder = Self::builder(&salt.salt);
+        builders.insert(0, (salt, builder));
+    }
+
+    /// Every salt that has been set, newest first
+    pub fn salts(&self) -> Vec<Salt> {
+        self.builders
+            .read()
+            .iter()
+            .map(|(s, _)| s.to_owned())
+            .collect()
+    }
+
+    /// Replaces the salts with ones that were previously saved, keeping their order
+    pub fn restore(&self, salts: Vec<Salt>) {
+        if salts.is_empty() {
+            return;
+        }
+
+        *self.builders.write() = salts
+            .into_iter()
+            .map(|s| {
+                let builder = Self::builder(&s.salt);
+                (s, builder)
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_encodes_the_salt_version() {
+        let hasher = Hasher::new();
+        hasher.set_salt("first");
+
+        let id = hasher.encode("5");
+
+        assert_eq!(Hasher::builder("first").decode(&id).unwrap(), vec![5, 1]);
+        assert_eq!(hasher.decode(&id), Some(5));
+    }
+
+    #[test]
+    fn it_decodes_ids_from_previous_salts() {
+        let hasher = Hasher::new();
+        hasher.set_salt("first");
+
+        let versioned_id = hasher.encode("5");
+
+        hasher.set_salt("second");
+
+        assert_ne!(hasher.encode("5"), versioned_id);
+        assert_eq!(hasher.decode(&versioned_id), Some(5));
+        assert_eq!(
+            hasher.salts(),
+            vec![
+                Salt {
+                    version: 2,
+                    salt: "second".into()
+                },
+                Salt {
+                    version: 1,
+                    salt: "first".into()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn it_keeps_legacy_ids_until_the_secret_changes() {
+        let hasher = Hasher::new();
+        hasher.set_secret("secret");
+
+        // Unversioned IDs were salted with the secret itself
+        let legacy_id = Hasher::builder("secret").encode(&[6]);
+
+        assert_eq!(hasher.encode("6"), legacy_id);
+        assert_eq!(hasher.decode(&legacy_id), Some(6));
+
+        hasher.set_secret("another secret");
+
+        let salts = hasher.salts();
+
+        assert_eq!(salts.len(), 2);
+        assert_eq!(salts[0].version, 1);
+        assert_ne!(salts[0].salt, "another secret");
+        assert_ne!(hasher.encode("6"), legacy_id);
+
+        // The legacy salt is saved, so its IDs survive a restart after the key changed
+        let restored = Hasher::new();
+        restored.restore(salts);
+        restored.set_secret("another secret");
+
+        assert_eq!(restored.decode(&legacy_id), Some(6));
+        assert_eq!(restored.encode("6"), hasher.encode("6"));
+
+        // Going back to the original secret goes back to the original IDs
+        restored.set_secret("secret");
+
+        assert_eq!(restored.encode("6"), legacy_id);
+        assert_eq!(restored.salts().len(), 2);
+    }
+
+    #[test]
+    fn it_restores_saved_salts() {
+        let hasher = Hasher::new();
+        hasher.set_salt("first");
+        hasher.set_salt("second");
+
+        let restored = Hasher::new();
+        restored.restore(hasher.salts());
+
+        // Setting the current salt again doesn't create a new version
+        restored.set_salt("second");
+
+        assert_eq!(restored.salts(), hasher.salts());
+        assert_eq!(restored.encode("5"), hasher.encode("5"));
     }
 }
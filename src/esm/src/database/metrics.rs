@@ -0,0 +1,211 @@
+use super::*;
+
+use parking_lot::Mutex as ParkingMutex;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::time::Duration;
+
+#[derive(Clone, Default)]
+pub struct Metrics {
+    queries: Arc<ParkingMutex<HashMap<String, QueryTiming>>>,
+}
+
+#[derive(Debug, Default)]
+struct QueryTiming {
+    count: u64,
+    error_count: u64,
+    cache_hits: u64,
+    cache_misses: u64,
+    max: Duration,
+
+    // The most recent execution times, used for the percentiles
+    samples: VecDeque<Duration>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct QueryReport {
+    pub name: String,
+    pub count: u64,
+    pub error_count: u64,
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub max_ms: f64,
+}
+
+impl Metrics {
+    const SAMPLE_SIZE: usize = 500;
+    const PARAMETERS_MAX_LENGTH: usize = 256;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, name: &str, elapsed: Duration, successful: bool) {
+        let mut queries = self.queries.lock();
+        let timing = queries.entry(name.to_owned()).or_default();
+
+        timing.count += 1;
+
+        if !successful {
+            timing.error_count += 1;
+        }
+
+        if elapsed > timing.max {
+            timing.max = elapsed;
+        }
+
+        if timing.samples.len() >= Self::SAMPLE_SIZE {
+            timing.samples.pop_front();
+        }
+
+        timing.samples.push_back(elapsed);
+    }
+
+    /// Cache hits are not executed, so they are counted separately from the timings
+    pub fn record_cache(&self, name: &str, hit: bool) {
+        let mut queries = self.queries.lock();
+        let timing = queries.entry(name.to_owned()).or_default();
+
+        if hit {
+            timing.cache_hits += 1;
+        } else {
+            timing.cache_misses += 1;
+        }
+    }
+
+    /// Returns the timings for every query that has been executed, slowest first
+    pub fn report(&self) -> Vec<QueryReport> {
+        let queries = self.queries.lock();
+
+        let mut reports: Vec<QueryReport> = queries
+            .iter()
+            .map(|(name, timing)| {
+                let mut samples: Vec<Duration> =
+                    timing.samples.iter().copied().collect();
+
+                samples.sort();
+
+                QueryReport {
+                    name: name.to_owned(),
+                    count: timing.count,
+                    error_count: timing.error_count,
+                    cache_hits: timing.cache_hits,
+                    cache_misses: timing.cache_misses,
+                    p50_ms: as_milliseconds(percentile(&samples, 50)),
+                    p95_ms: as_milliseconds(percentile(&samples, 95)),
+                    max_ms: as_milliseconds(timing.max),
+                }
+            })
+            .collect();
+
+        reports.sort_by(|a, b| b.p95_ms.total_cmp(&a.p95_ms));
+        reports
+    }
+
+    /// Formats the query parameters so they are safe to write to a single log line
+    pub fn sanitize<P: Debug>(parameters: &P) -> String {
+        let parameters = format!("{parameters:?}")
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+
+        if parameters.chars().count() <= Self::PARAMETERS_MAX_LENGTH {
+            return parameters;
+        }
+
+        let truncated: String = parameters
+            .chars()
+            .take(Self::PARAMETERS_MAX_LENGTH)
+            .collect();
+
+        format!("{truncated}...")
+    }
+}
+
+// Expects the samples to be sorted
+fn percentile(samples: &[Duration], percentile: usize) -> Duration {
+    if samples.is_empty() {
+        return Duration::ZERO;
+    }
+
+    let index = (samples.len() * percentile).div_ceil(100);
+    samples[index.saturating_sub(1).min(samples.len() - 1)]
+}
+
+fn as_milliseconds(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reports_query_timings() {
+        let metrics = Metrics::new();
+
+        for i in 1..=100 {
+            metrics.record("command_me", Duration::from_millis(i), i != 100);
+        }
+
+        metrics.record("command_all_territories", Duration::from_millis(500), true);
+
+        let report = metrics.report();
+
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].name, "command_all_territories");
+        assert_eq!(report[1].name, "command_me");
+        assert_eq!(report[1].count, 100);
+        assert_eq!(report[1].error_count, 1);
+        assert_eq!(report[1].p50_ms, 50.0);
+        assert_eq!(report[1].p95_ms, 95.0);
+        assert_eq!(report[1].max_ms, 100.0);
+    }
+
+    #[test]
+    fn it_only_keeps_recent_samples() {
+        let metrics = Metrics::new();
+
+        metrics.record("command_me", Duration::from_secs(10), true);
+
+        for _ in 0..Metrics::SAMPLE_SIZE {
+            metrics.record("command_me", Duration::from_millis(1), true);
+        }
+
+        let report = metrics.report();
+
+        assert_eq!(report[0].p95_ms, 1.0);
+        assert_eq!(report[0].max_ms, 10_000.0);
+    }
+
+    #[test]
+    fn it_counts_cache_hits_separately() {
+        let metrics = Metrics::new();
+
+        metrics.record_cache("command_territory_info", false);
+        metrics.record("command_territory_info", Duration::from_millis(10), true);
+        metrics.record_cache("command_territory_info", true);
+        metrics.record_cache("command_territory_info", true);
+
+        let report = metrics.report();
+
+        assert_eq!(report[0].count, 1);
+        assert_eq!(report[0].cache_hits, 2);
+        assert_eq!(report[0].cache_misses, 1);
+    }
+
+    #[test]
+    fn it_sanitizes_parameters() {
+        let arguments =
+            HashMap::from([("uid".to_string(), "7656\n1198".to_string())]);
+        assert_eq!(Metrics::sanitize(&arguments), r#"{"uid": "7656\n1198"}"#);
+
+        let long = "a".repeat(300);
+        let sanitized = Metrics::sanitize(&long);
+
+        assert_eq!(sanitized.len(), Metrics::PARAMETERS_MAX_LENGTH + 3);
+        assert!(sanitized.ends_with("..."));
+    }
+}
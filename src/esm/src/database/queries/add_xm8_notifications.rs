@@ -1,24 +1,29 @@
 use uuid::Uuid;
 
 use super::*;
+use mysql_async::Value;
 
 // Limit tampering
 fn query() -> &'static str {
     r#"
     INSERT INTO
-        xm8_notification (uuid, recipient_uid, territory_id, type, content)
+        xm8_notification (uuid, recipient_uid, territory_id, type, content, suppressed_count, deliver_at)
     VALUES
-        (:uuid, :uid, :territory_id, :type, :content);
+        (:uuid, :uid, :territory_id, :type, :content, :suppressed_count, :deliver_at);
     "#
 }
 
 pub async fn add_xm8_notifications(
     context: &Database,
-    connection: &mut Conn,
-    notification_type: String,
+    connection: &mut dyn Executor,
+    notification_type: NotificationType,
     recipient_uids: String,
     mut content: HashMap<String, String>,
 ) -> Result<(), Error> {
+    // The bot can't render a notification that is missing content
+    notification_type.validate(&content)?;
+    let notification_type = notification_type.as_str();
+
     let territory_id = &content.remove("territory_id");
 
     // If the XM8 notification comes with a territory ID, we need to encode it
@@ -35,24 +40,440 @@ pub async fn add_xm8_notifications(
 
     let content = serde_json::to_string(&content).map_err(|e| e.to_string())?;
 
+    if recipient_uids.is_empty() {
+        return Ok(());
+    }
+
+    // Players who turned this type off don't receive it. Anyone in their quiet hours
+    // receives it once their quiet hours end
+    let disabled_uids =
+        disabled_recipients(context, connection, notification_type, &recipient_uids)
+            .await?;
+
+    let recipient_uids: Vec<String> = recipient_uids
+        .into_iter()
+        .filter(|uid| !disabled_uids.contains(uid))
+        .collect();
+
+    if recipient_uids.is_empty() {
+        return Ok(());
+    }
+
+    // Repeated events, such as a raid in progress, are merged into the notification that was
+    // already created for them instead of spamming the player
+    let recipient_uids = throttle_recipients(
+        context,
+        connection,
+        notification_type,
+        territory_id,
+        recipient_uids,
+    )
+    .await?;
+
+    if recipient_uids.is_empty() {
+        return Ok(());
+    }
+
+    let suppressed_counts = suppressed_counts(
+        context,
+        connection,
+        notification_type,
+        territory_id,
+        &recipient_uids,
+    )
+    .await?;
+
+    let quiet_hours = quiet_hours(context, connection, &recipient_uids).await?;
+    let now = Utc::now().naive_utc();
+
+    let recipients: Vec<(String, Option<NaiveDateTime>)> = recipient_uids
+        .into_iter()
+        .map(|uid| {
+            let deliver_at =
+                quiet_hours.get(&uid).and_then(|(starts_at, ends_at)| {
+                    quiet_hours_end(now, *starts_at, *ends_at)
+                });
+
+            (uid, deliver_at)
+        })
+        .collect();
+
     // Execute the query
     let result = connection
         .exec_batch(
             query(),
-            recipient_uids.iter().map(|uid| {
+            recipients.iter().map(|(uid, deliver_at)| {
+                let suppressed_count =
+                    suppressed_counts.get(uid).copied().unwrap_or(0);
+
                 params! {
                     "uuid" => Uuid::new_v4().to_string(),
                     "uid" => &uid,
                     "territory_id" => territory_id,
-                    "type" => &notification_type,
+                    "type" => notification_type,
                     "content" => &content,
+                    "suppressed_count" => suppressed_count,
+                    "deliver_at" => deliver_at,
                 }
             }),
         )
         .await;
 
-    match result {
-        Ok(_) => Ok(()),
-        Err(e) => Err(e.to_string().into()),
+    if let Err(e) = result {
+        return Err(e.to_string().into());
+    }
+
+    // The held back notifications have been folded into this one
+    let folded: Vec<&String> = recipients
+        .iter()
+        .map(|(uid, _)| uid)
+        .filter(|uid| suppressed_counts.contains_key(*uid))
+        .collect();
+
+    if folded.is_empty() {
+        return Ok(());
+    }
+
+    connection
+        .exec_batch(
+            &context.sql.clear_xm8_suppressed_notification,
+            folded.into_iter().map(|uid| {
+                params! {
+                    "uid" => uid,
+                    "territory_id" => territory_key(territory_id),
+                    "type" => notification_type,
+                }
+            }),
+        )
+        .await
+        .map_err(|e| e.to_string().into())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Throttle {
+    /// Create a new notification
+    Deliver,
+
+    /// Count it against a notification that hasn't been sent yet
+    Merge(u64),
+
+    /// Count it against the next notification that is created
+    Hold,
+}
+
+/// Decides what happens to a notification for a single recipient.
+/// `duplicate` is the most recent notification of the same type about the same territory
+/// within the dedup window and its state. `recent_count` is how many notifications the
+/// recipient received about the territory within the rate limit window
+pub fn throttle(
+    duplicate: Option<&(u64, String)>,
+    recent_count: u64,
+    rate_limit: u64,
+) -> Throttle {
+    match duplicate {
+        Some((id, state)) if state == "new" => Throttle::Merge(*id),
+        Some(_) => Throttle::Hold,
+        None if rate_limit > 0 && recent_count >= rate_limit => Throttle::Hold,
+        None => Throttle::Deliver,
+    }
+}
+
+// Returns the recipients that should receive a new notification, recording the rest
+async fn throttle_recipients(
+    context: &Database,
+    connection: &mut dyn Executor,
+    notification_type: &str,
+    territory_id: &Option<String>,
+    recipient_uids: Vec<String>,
+) -> Result<Vec<String>, Error> {
+    let dedup_seconds = crate::CONFIG
+        .xm8_notification_dedup_seconds
+        .get(notification_type)
+        .copied()
+        .unwrap_or(0);
+
+    // The rate limit is per territory. Notifications that aren't about one are never limited
+    let rate_limit = match territory_id {
+        Some(_) => crate::CONFIG.xm8_notification_rate_limit,
+        None => 0,
+    };
+    let rate_limit_seconds = crate::CONFIG.xm8_notification_rate_limit_seconds;
+
+    let mut duplicates: HashMap<String, (u64, String)> = HashMap::new();
+    if dedup_seconds > 0 {
+        let query = replace_list(
+            &context.sql.xm8_recent_duplicates_lookup,
+            ":uids",
+            recipient_uids.len(),
+        );
+
+        let mut params: Vec<Value> = vec![
+            Value::from(notification_type),
+            Value::from(territory_id.clone()),
+            Value::from(dedup_seconds),
+        ];
+        params.extend(recipient_uids.iter().map(Value::from));
+
+        let rows = connection
+            .exec_map(&query, params, |row: (String, u64, String)| row)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        // Ordered newest first
+        for (uid, id, state) in rows {
+            duplicates.entry(uid).or_insert((id, state));
+        }
+    }
+
+    let mut recent_counts: HashMap<String, u64> = HashMap::new();
+    if rate_limit > 0 {
+        let query = replace_list(
+            &context.sql.xm8_recent_counts_lookup,
+            ":uids",
+            recipient_uids.len(),
+        );
+
+        let mut params: Vec<Value> = vec![
+            Value::from(territory_id.clone()),
+            Value::from(rate_limit_seconds),
+        ];
+        params.extend(recipient_uids.iter().map(Value::from));
+
+        recent_counts = connection
+            .exec_map(&query, params, |row: (String, u64)| row)
+            .await
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .collect();
+    }
+
+    let mut deliver = vec![];
+    let mut merge = vec![];
+    let mut hold = vec![];
+
+    for uid in recipient_uids {
+        match throttle(
+            duplicates.get(&uid),
+            recent_counts.get(&uid).copied().unwrap_or(0),
+            rate_limit,
+        ) {
+            Throttle::Deliver => deliver.push(uid),
+            Throttle::Merge(id) => merge.push(id),
+            Throttle::Hold => hold.push(uid),
+        }
+    }
+
+    if !merge.is_empty() {
+        connection
+            .exec_batch(
+                &context.sql.merge_xm8_notification,
+                merge.into_iter().map(|id| params! { "id" => id }),
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    if !hold.is_empty() {
+        debug!(
+            "[add_xm8_notifications] Holding {notification_type} for {} recipients",
+            hold.len()
+        );
+
+        connection
+            .exec_batch(
+                &context.sql.add_xm8_suppressed_notification,
+                hold.iter().map(|uid| {
+                    params! {
+                        "uid" => uid,
+                        "territory_id" => territory_key(territory_id),
+                        "type" => notification_type,
+                    }
+                }),
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(deliver)
+}
+
+// How many notifications were held back for each recipient since their last one
+async fn suppressed_counts(
+    context: &Database,
+    connection: &mut dyn Executor,
+    notification_type: &str,
+    territory_id: &Option<String>,
+    recipient_uids: &[String],
+) -> Result<HashMap<String, u64>, Error> {
+    let query = replace_list(
+        &context.sql.xm8_suppressed_lookup,
+        ":uids",
+        recipient_uids.len(),
+    );
+
+    let mut params: Vec<Value> = vec![
+        Value::from(territory_key(territory_id)),
+        Value::from(notification_type),
+    ];
+    params.extend(recipient_uids.iter().map(Value::from));
+
+    connection
+        .exec_map(&query, params, |row: (String, u64)| row)
+        .await
+        .map(|counts| counts.into_iter().collect())
+        .map_err(|e| e.to_string().into())
+}
+
+// Held back notifications that aren't about a territory are stored under 0
+fn territory_key(territory_id: &Option<String>) -> &str {
+    territory_id.as_deref().unwrap_or("0")
+}
+
+async fn disabled_recipients(
+    context: &Database,
+    connection: &mut dyn Executor,
+    notification_type: &str,
+    recipient_uids: &[String],
+) -> Result<Vec<String>, Error> {
+    let query = replace_list(
+        &context.sql.xm8_disabled_recipients_lookup,
+        ":uids",
+        recipient_uids.len(),
+    );
+
+    let mut params: Vec<&str> = vec![notification_type];
+    params.extend(recipient_uids.iter().map(String::as_str));
+
+    connection
+        .exec_map(&query, params, |uid: String| uid)
+        .await
+        .map_err(|e| e.to_string().into())
+}
+
+async fn quiet_hours(
+    context: &Database,
+    connection: &mut dyn Executor,
+    recipient_uids: &[String],
+) -> Result<HashMap<String, (NaiveTime, NaiveTime)>, Error> {
+    let query = replace_list(
+        &context.sql.xm8_quiet_hours_lookup,
+        ":uids",
+        recipient_uids.len(),
+    );
+
+    connection
+        .exec_map(
+            &query,
+            recipient_uids.to_vec(),
+            |(uid, starts_at, ends_at): (String, NaiveTime, NaiveTime)| {
+                (uid, (starts_at, ends_at))
+            },
+        )
+        .await
+        .map(|quiet_hours| quiet_hours.into_iter().collect())
+        .map_err(|e| e.to_string().into())
+}
+
+/// When quiet hours that contain `now` end, or None if `now` is outside of them.
+/// Everything is in UTC and quiet hours may wrap past midnight
+pub fn quiet_hours_end(
+    now: NaiveDateTime,
+    starts_at: NaiveTime,
+    ends_at: NaiveTime,
+) -> Option<NaiveDateTime> {
+    let time = now.time();
+    let today = now.date();
+
+    if starts_at < ends_at {
+        // 01:00 to 07:00
+        (time >= starts_at && time < ends_at).then_some(today.and_time(ends_at))
+    } else if starts_at > ends_at && time >= starts_at {
+        // 22:00 to 07:00, before midnight
+        today.succ_opt().map(|tomorrow| tomorrow.and_time(ends_at))
+    } else if starts_at > ends_at && time < ends_at {
+        // 22:00 to 07:00, after midnight
+        Some(today.and_time(ends_at))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 12, day)
+            .unwrap()
+            .and_time(time(hour, minute))
+    }
+
+    #[test]
+    fn it_delays_until_quiet_hours_end() {
+        assert_eq!(
+            quiet_hours_end(at(20, 3, 0), time(1, 0), time(7, 0)),
+            Some(at(20, 7, 0))
+        );
+        assert_eq!(quiet_hours_end(at(20, 7, 0), time(1, 0), time(7, 0)), None);
+    }
+
+    #[test]
+    fn it_handles_quiet_hours_past_midnight() {
+        assert_eq!(
+            quiet_hours_end(at(20, 23, 30), time(22, 0), time(7, 0)),
+            Some(at(21, 7, 0))
+        );
+        assert_eq!(
+            quiet_hours_end(at(21, 2, 0), time(22, 0), time(7, 0)),
+            Some(at(21, 7, 0))
+        );
+        assert_eq!(
+            quiet_hours_end(at(21, 12, 0), time(22, 0), time(7, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn it_throttles_repeated_notifications() {
+        let new = (12, "new".to_string());
+        let sent = (12, "sent".to_string());
+
+        assert_eq!(throttle(None, 0, 10), Throttle::Deliver);
+        assert_eq!(throttle(Some(&new), 0, 10), Throttle::Merge(12));
+        assert_eq!(throttle(Some(&sent), 0, 10), Throttle::Hold);
+        assert_eq!(throttle(None, 10, 10), Throttle::Hold);
+        assert_eq!(throttle(None, 10, 0), Throttle::Deliver);
+    }
+
+    #[tokio::test]
+    async fn it_only_rate_limits_territory_notifications() {
+        let database = crate::database::fake::Fixture::database();
+        let mut fake = crate::database::fake::FakeExecutor::new();
+
+        let recipients = vec!["76561198037177305".to_string()];
+
+        // No dedup window is set for this type, and without a territory there is nothing
+        // to count against the rate limit
+        let deliver = throttle_recipients(
+            &database,
+            &mut fake,
+            "custom",
+            &None,
+            recipients.clone(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(deliver, recipients);
+        assert!(fake.statements.is_empty());
+    }
+
+    #[test]
+    fn it_ignores_empty_quiet_hours() {
+        assert_eq!(quiet_hours_end(at(20, 3, 0), time(3, 0), time(3, 0)), None);
     }
 }
@@ -0,0 +1,21 @@
+use super::*;
+
+pub async fn check_if_account_exists(
+    context: &Database,
+    connection: &mut dyn Executor,
+    steam_uid: &str,
+) -> Result<bool, Error> {
+    let existence_check: Option<String> = connection
+        .exec_first(
+            &context.sql.check_if_account_exists,
+            params! {
+                "uid" => steam_uid
+            },
+        )
+        .await?;
+
+    match existence_check {
+        Some(exists) => Ok(exists == "true"),
+        None => Ok(false),
+    }
+}
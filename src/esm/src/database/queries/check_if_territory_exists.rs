@@ -2,7 +2,7 @@ use super::*;
 
 pub async fn check_if_territory_exists(
     context: &Database,
-    connection: &mut Conn,
+    connection: &mut dyn Executor,
     database_id: u64,
 ) -> Result<bool, Error> {
     let existence_check: Option<String> = connection
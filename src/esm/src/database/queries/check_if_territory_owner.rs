@@ -2,7 +2,7 @@ use super::*;
 
 pub async fn check_if_territory_owner(
     context: &Database,
-    connection: &mut Conn,
+    connection: &mut dyn Executor,
     territory_id: u64,
     steam_uid: &str,
 ) -> Result<bool, Error> {
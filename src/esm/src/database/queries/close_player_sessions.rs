@@ -0,0 +1,14 @@
+use super::*;
+
+/// Closes every session that is still open. Called on post init, when nobody can be
+/// connected yet. Like close_stale_player_sessions, their real end is unknown so they don't
+/// count towards playtime. Returns the number of sessions closed
+pub async fn close_player_sessions(
+    context: &Database,
+    connection: &mut dyn Executor,
+) -> Result<u64, Error> {
+    connection
+        .execute(&context.sql.close_all_player_sessions, Params::Empty)
+        .await
+        .map_err(|e| e.to_string().into())
+}
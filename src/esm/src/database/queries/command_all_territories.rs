@@ -1,45 +1,489 @@
 use super::*;
 
+use base64::prelude::*;
+use mysql_async::Value;
+
+const DEFAULT_LIMIT: usize = 25;
+const MAX_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Sort {
+    Id,
+    Level,
+    ObjectCount,
+    LastPaidAt,
+    Name,
+}
+
+impl Sort {
+    fn parse(sort: &str) -> Result<Self, QueryError> {
+        match sort {
+            "id" => Ok(Sort::Id),
+            "level" => Ok(Sort::Level),
+            "object_count" => Ok(Sort::ObjectCount),
+            "last_paid_at" => Ok(Sort::LastPaidAt),
+            "name" => Ok(Sort::Name),
+            _ => Err(QueryError::User(format!(
+                "`sort` must be one of: id, level, object_count, last_paid_at, name. Got \"{sort}\""
+            ))),
+        }
+    }
+
+    // These are inserted into the query as is. Never allow user input here
+    fn expression(&self) -> &'static str {
+        match self {
+            Sort::Id => "t.id",
+            Sort::Level => "t.level",
+            Sort::ObjectCount => {
+                "(SELECT COUNT(*) FROM construction c WHERE c.territory_id = t.id)"
+            }
+            Sort::LastPaidAt => "COALESCE(t.last_paid_at, t.created_at)",
+            Sort::Name => "t.name",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Order {
+    Asc,
+    Desc,
+}
+
+impl Order {
+    fn parse(order: &str) -> Result<Self, QueryError> {
+        match order {
+            "asc" => Ok(Order::Asc),
+            "desc" => Ok(Order::Desc),
+            _ => Err(QueryError::User(format!(
+                "`order` must be either asc or desc. Got \"{order}\""
+            ))),
+        }
+    }
+}
+
+/// Points to the last territory on a page. The next page starts after it
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Cursor {
+    sort: Sort,
+    order: Order,
+    value: JSONValue,
+    id: u64,
+}
+
+impl Cursor {
+    fn encode(&self) -> String {
+        let json = serde_json::to_string(self).unwrap_or_default();
+        BASE64_URL_SAFE_NO_PAD.encode(json)
+    }
+
+    fn decode(cursor: &str) -> Result<Self, QueryError> {
+        let invalid = || QueryError::User("`cursor` is not valid".into());
+
+        let json = BASE64_URL_SAFE_NO_PAD
+            .decode(cursor)
+            .map_err(|_| invalid())?;
+
+        serde_json::from_slice(&json).map_err(|_| invalid())
+    }
+
+    fn sql_value(&self) -> Value {
+        match &self.value {
+            JSONValue::Number(n) => match n.as_i64() {
+                Some(n) => Value::Int(n),
+                None => Value::Double(n.as_f64().unwrap_or_default()),
+            },
+            JSONValue::String(s) => Value::from(s),
+            _ => Value::NULL,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct TerritoryResult {
+    id: String,
+    esm_custom_id: Option<String>,
+    territory_name: String,
+    owner_uid: String,
+    owner_name: String,
+    level: isize,
+    object_count: isize,
+    last_paid_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize)]
+struct Page {
+    territories: Vec<TerritoryResult>,
+    limit: usize,
+    sort: Sort,
+    order: Order,
+    has_more: bool,
+    next_cursor: Option<String>,
+}
+
+/// Returns a page of territories. Pass `next_cursor` back as `cursor` for the next page
+/// Without any of `limit`, `sort`, `order`, or `cursor`, every territory is returned as its
+/// own result instead, which is what older bots expect
+/// Arguments (all optional):
+///     limit: 1 to 100, defaults to 25
+///     sort: id, level, object_count, last_paid_at, or name. Defaults to id
+///     order: asc or desc. Defaults to asc
+///     cursor: The `next_cursor` from the previous page
+///     owner_uid: Only territories owned by this player
+///     min_level: Only territories at this level or higher
+///     overdue: "true" for only territories that have not been paid for within the
+///         territory lifetime
 pub async fn command_all_territories(
     context: &Database,
-    connection: &mut Conn,
-    _arguments: &HashMap<String, String>,
+    connection: &mut dyn Executor,
+    arguments: &HashMap<String, String>,
 ) -> QueryResult {
-    #[derive(Debug, Serialize)]
-    struct TerritoryResult {
-        id: String,
-        esm_custom_id: Option<String>,
-        territory_name: String,
-        owner_uid: String,
-        owner_name: String,
+    let paginated = ["limit", "sort", "order", "cursor"]
+        .iter()
+        .any(|key| arguments.contains_key(*key));
+
+    let limit = match arguments.get("limit") {
+        Some(limit) => match limit.parse::<usize>() {
+            Ok(limit) if (1..=MAX_LIMIT).contains(&limit) => limit,
+            _ => {
+                return Err(QueryError::User(format!(
+                    "`limit` must be a number between 1 and {MAX_LIMIT}"
+                )))
+            }
+        },
+        None => DEFAULT_LIMIT,
+    };
+
+    let cursor = match arguments.get("cursor") {
+        Some(cursor) if !cursor.is_empty() => Some(Cursor::decode(cursor)?),
+        _ => None,
+    };
+
+    // A cursor only makes sense for the sort it was created with
+    let sort = match (arguments.get("sort"), &cursor) {
+        (Some(sort), _) => Sort::parse(sort)?,
+        (None, Some(cursor)) => cursor.sort,
+        (None, None) => Sort::Id,
+    };
+
+    let order = match (arguments.get("order"), &cursor) {
+        (Some(order), _) => Order::parse(order)?,
+        (None, Some(cursor)) => cursor.order,
+        (None, None) => Order::Asc,
+    };
+
+    if let Some(cursor) = &cursor {
+        if cursor.sort != sort || cursor.order != order {
+            return Err(QueryError::User(
+                "`cursor` was created with a different sort or order".into(),
+            ));
+        }
     }
 
+    let min_level =
+        match arguments.get("min_level") {
+            Some(level) => Some(level.parse::<isize>().map_err(|_| {
+                QueryError::User("`min_level` must be a number".into())
+            })?),
+            None => None,
+        };
+
+    let overdue = arguments
+        .get("overdue")
+        .map(|o| o == "true")
+        .unwrap_or(false);
+
+    // Every territory would be overdue until the server sends its lifetime on init
+    let territory_lifetime = crate::bot::territory_lifetime();
+    if overdue && territory_lifetime == 0 {
+        return Err(QueryError::System(
+            "Territory lifetime is not known yet. Has the server sent init?".into(),
+        ));
+    }
+
+    let (direction, operator) = match order {
+        Order::Asc => ("ASC", ">"),
+        Order::Desc => ("DESC", "<"),
+    };
+
+    let query = context
+        .sql
+        .command_all_territories
+        .replace(":sort_expression", sort.expression())
+        .replace(":sort_direction", direction)
+        .replace(":cursor_operator", operator);
+
+    let cursor_value = match &cursor {
+        Some(cursor) => cursor.sql_value(),
+        None => Value::NULL,
+    };
+
+    // One extra to know if there is another page. Older bots get everything
+    let row_limit = if paginated {
+        limit as u64 + 1
+    } else {
+        u64::MAX
+    };
+
     let result = connection
         .exec_map(
-            &context.sql.command_all_territories,
-            Params::Empty,
-            |(id, esm_custom_id, territory_name, owner_uid, owner_name)| {
-                let id: String = id;
-                TerritoryResult {
-                    id: context.encode_territory_id(&id),
-                    esm_custom_id,
-                    territory_name,
-                    owner_uid,
-                    owner_name,
-                }
+            &query,
+            params! {
+                "owner_uid" => arguments.get("owner_uid").cloned(),
+                "min_level" => min_level,
+                "overdue" => overdue,
+                "territory_lifetime" => territory_lifetime,
+                "cursor_id" => cursor.as_ref().map(|c| c.id),
+                "cursor_value" => cursor_value,
+                "limit" => row_limit
             },
+            |row: Row| map_results(context, row),
         )
         .await;
 
-    match result {
-        Ok(r) => {
-            let results: Vec<String> = r
-                .into_iter()
-                .filter_map(|t| serde_json::to_string(&t).ok())
-                .collect();
+    let mut rows = match result {
+        Ok(rows) => rows
+            .into_iter()
+            .collect::<Result<Vec<(TerritoryResult, Value, u64)>, String>>()
+            .map_err(|e| QueryError::System(format!("Query failed - {e}")))?,
+        Err(e) => return Err(QueryError::System(format!("Query failed - {}", e))),
+    };
 
-            Ok(results)
-        }
+    if !paginated {
+        return rows
+            .into_iter()
+            .map(|(territory, _, _)| {
+                serde_json::to_string(&territory)
+                    .map_err(|e| QueryError::System(format!("Query failed - {}", e)))
+            })
+            .collect();
+    }
+
+    let has_more = rows.len() > limit;
+    rows.truncate(limit);
+
+    let next_cursor = match rows.last() {
+        Some((_, value, id)) if has_more => Some(
+            Cursor {
+                sort,
+                order,
+                value: json_value(value),
+                id: *id,
+            }
+            .encode(),
+        ),
+        _ => None,
+    };
+
+    let page = Page {
+        territories: rows.into_iter().map(|(t, _, _)| t).collect(),
+        limit,
+        sort,
+        order,
+        has_more,
+        next_cursor,
+    };
+
+    match serde_json::to_string(&page) {
+        Ok(page) => Ok(vec![page]),
         Err(e) => Err(QueryError::System(format!("Query failed - {}", e))),
     }
 }
+
+fn map_results(
+    context: &Database,
+    row: Row,
+) -> Result<(TerritoryResult, Value, u64), String> {
+    let id: String = select_column(&row, "id")?;
+    let database_id = id.parse::<u64>().map_err(|e| e.to_string())?;
+
+    let territory = TerritoryResult {
+        id: context.encode_territory_id(&id),
+        esm_custom_id: select_column(&row, "esm_custom_id")?,
+        territory_name: select_column(&row, "name")?,
+        owner_uid: select_column(&row, "owner_uid")?,
+        owner_name: select_column(&row, "owner_name")?,
+        level: select_column(&row, "level")?,
+        object_count: select_column(&row, "object_count")?,
+        last_paid_at: select_column(&row, "last_paid_at")?,
+    };
+
+    let sort_value: Value = select_column(&row, "sort_value")?;
+
+    Ok((territory, sort_value, database_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+
+    fn territory(id: &str, name: &str) -> Row {
+        Fixture::result(&[
+            ("id", Value::from(id)),
+            ("esm_custom_id", Value::NULL),
+            ("name", Value::from(name)),
+            ("owner_uid", Value::from("76561198037177305")),
+            ("owner_name", Value::from("Bryan")),
+            ("level", Value::Int(1)),
+            ("object_count", Value::Int(10)),
+            ("last_paid_at", Value::Date(2024, 12, 20, 0, 0, 0, 0)),
+            ("sort_value", Value::from(name)),
+        ])
+    }
+
+    fn query(database: &Database) -> String {
+        database
+            .sql
+            .command_all_territories
+            .replace(":sort_expression", Sort::Name.expression())
+            .replace(":sort_direction", "ASC")
+            .replace(":cursor_operator", ">")
+    }
+
+    #[tokio::test]
+    async fn it_returns_a_page_with_a_cursor() {
+        let database = Fixture::database();
+
+        let mut fake = FakeExecutor::new().respond_with(
+            &query(&database),
+            vec![
+                territory("1", "Alpha"),
+                territory("2", "Bravo"),
+                territory("3", "Charlie"),
+            ],
+        );
+
+        let arguments = HashMap::from([
+            ("sort".to_string(), "name".to_string()),
+            ("limit".to_string(), "2".to_string()),
+        ]);
+
+        let results = command_all_territories(&database, &mut fake, &arguments)
+            .await
+            .unwrap();
+
+        let page: JSONValue = serde_json::from_str(&results[0]).unwrap();
+
+        assert_eq!(page["territories"].as_array().unwrap().len(), 2);
+        assert_eq!(page["has_more"], json!(true));
+        assert_eq!(page["sort"], json!("name"));
+
+        let cursor = Cursor::decode(page["next_cursor"].as_str().unwrap()).unwrap();
+
+        assert_eq!(
+            cursor,
+            Cursor {
+                sort: Sort::Name,
+                order: Order::Asc,
+                value: json!("Bravo"),
+                id: 2
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn it_returns_the_last_page_without_a_cursor() {
+        let database = Fixture::database();
+
+        let mut fake = FakeExecutor::new()
+            .respond_with(&query(&database), vec![territory("3", "Charlie")]);
+
+        let cursor = Cursor {
+            sort: Sort::Name,
+            order: Order::Asc,
+            value: json!("Bravo"),
+            id: 2,
+        };
+
+        let arguments = HashMap::from([("cursor".to_string(), cursor.encode())]);
+
+        let results = command_all_territories(&database, &mut fake, &arguments)
+            .await
+            .unwrap();
+
+        let page: JSONValue = serde_json::from_str(&results[0]).unwrap();
+
+        assert_eq!(page["has_more"], json!(false));
+        assert_eq!(page["next_cursor"], JSONValue::Null);
+    }
+
+    #[tokio::test]
+    async fn it_returns_every_territory_without_paging_arguments() {
+        let database = Fixture::database();
+
+        let query = database
+            .sql
+            .command_all_territories
+            .replace(":sort_expression", Sort::Id.expression())
+            .replace(":sort_direction", "ASC")
+            .replace(":cursor_operator", ">");
+
+        let territories = (1..=30)
+            .map(|id| territory(&id.to_string(), &format!("Territory {id}")))
+            .collect();
+
+        let mut fake = FakeExecutor::new().respond_with(&query, territories);
+
+        let results = command_all_territories(&database, &mut fake, &HashMap::new())
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 30);
+
+        let territory: JSONValue = serde_json::from_str(&results[0]).unwrap();
+        assert_eq!(territory["territory_name"], json!("Territory 1"));
+        assert_eq!(territory.get("has_more"), None);
+    }
+
+    #[tokio::test]
+    async fn it_rejects_invalid_arguments() {
+        let database = Fixture::database();
+        let mut fake = FakeExecutor::new();
+
+        let cursor = Cursor {
+            sort: Sort::Name,
+            order: Order::Asc,
+            value: json!("Bravo"),
+            id: 2,
+        };
+
+        for (key, value) in [
+            ("limit", "0".to_string()),
+            ("limit", "101".to_string()),
+            ("sort", "owner".to_string()),
+            ("order", "up".to_string()),
+            ("cursor", "not a cursor".to_string()),
+            ("min_level", "one".to_string()),
+        ] {
+            let arguments = HashMap::from([(key.to_string(), value)]);
+            let result =
+                command_all_territories(&database, &mut fake, &arguments).await;
+
+            assert!(matches!(result, Err(QueryError::User(_))), "{}", key);
+        }
+
+        let arguments = HashMap::from([
+            ("cursor".to_string(), cursor.encode()),
+            ("sort".to_string(), "level".to_string()),
+        ]);
+
+        let result = command_all_territories(&database, &mut fake, &arguments).await;
+        assert!(matches!(result, Err(QueryError::User(_))));
+        assert!(fake.statements.is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_waits_for_the_territory_lifetime_to_find_overdue_territories() {
+        let database = Fixture::database();
+        let mut fake = FakeExecutor::new();
+
+        // The server hasn't sent init in tests, so the lifetime is unknown
+        let arguments = HashMap::from([("overdue".to_string(), "true".to_string())]);
+        let result = command_all_territories(&database, &mut fake, &arguments).await;
+
+        assert!(matches!(result, Err(QueryError::System(e)) if e.contains("init")));
+        assert!(fake.statements.is_empty());
+    }
+}
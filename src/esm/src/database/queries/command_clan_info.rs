@@ -0,0 +1,186 @@
+use super::*;
+
+#[derive(Debug, Serialize)]
+pub struct Clan {
+    pub id: u64,
+    pub name: String,
+    pub leader_uid: String,
+    pub leader_name: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub map_marker_count: isize,
+    pub members: Vec<ClanMember>,
+    pub territories: Vec<ClanTerritory>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClanMember {
+    pub uid: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClanTerritory {
+    pub id: String,
+    pub name: String,
+    pub owner_uid: String,
+}
+
+pub async fn command_clan_info(
+    context: &Database,
+    connection: &mut dyn Executor,
+    arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let Some(clan_id) = arguments.get("clan_id") else {
+        return Err(QueryError::User(
+            "Missing key `clan_id` in provided query arguments".into(),
+        ));
+    };
+
+    let Ok(clan_id) = clan_id.parse::<u64>() else {
+        return Err(QueryError::User(format!(
+            "`clan_id` must be a number, got \"{clan_id}\""
+        )));
+    };
+
+    match find_clan(context, connection, clan_id).await? {
+        Some(clan) => Ok(vec![serde_json::to_string(&clan).unwrap()]),
+        None => Ok(vec![]),
+    }
+}
+
+/// Loads the clan along with its members and the territories they own
+/// Used by multiple clan commands
+pub async fn find_clan(
+    context: &Database,
+    connection: &mut dyn Executor,
+    clan_id: u64,
+) -> Result<Option<Clan>, QueryError> {
+    let clan: Option<Row> = connection
+        .exec_first(&context.sql.command_clan_info, params! { clan_id })
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    let Some(clan) = clan else {
+        return Ok(None);
+    };
+
+    let members = connection
+        .exec_map(
+            &context.sql.clan_members_lookup,
+            params! { clan_id },
+            |(uid, name)| ClanMember { uid, name },
+        )
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    let territories = connection
+        .exec_map(
+            &context.sql.clan_territories_lookup,
+            params! { clan_id },
+            |(id, name, owner_uid): (String, String, String)| ClanTerritory {
+                id: context.encode_territory_id(&id),
+                name,
+                owner_uid,
+            },
+        )
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    let clan = map_results(clan, members, territories)
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    Ok(Some(clan))
+}
+
+fn map_results(
+    row: Row,
+    members: Vec<ClanMember>,
+    territories: Vec<ClanTerritory>,
+) -> Result<Clan, String> {
+    Ok(Clan {
+        id: select_column(&row, "id")?,
+        name: select_column(&row, "name")?,
+        leader_uid: select_column(&row, "leader_uid")?,
+        leader_name: select_column(&row, "leader_name")?,
+        created_at: select_column(&row, "created_at")?,
+        map_marker_count: select_column(&row, "map_marker_count")?,
+        members,
+        territories,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+    use mysql_async::Value;
+
+    #[tokio::test]
+    async fn it_returns_the_clan_with_members_and_territories() {
+        let database = Fixture::database();
+
+        let mut fake = FakeExecutor::new()
+            .respond_with(
+                &database.sql.command_clan_info,
+                vec![Fixture::result(&[
+                    ("id", Value::Int(3)),
+                    ("name", Value::from("Knuckles")),
+                    ("leader_uid", Value::from("76561198037177305")),
+                    ("leader_name", Value::from("Bryan")),
+                    ("created_at", Value::Date(2024, 12, 20, 0, 0, 0, 0)),
+                    ("map_marker_count", Value::Int(2)),
+                ])],
+            )
+            .respond_with(
+                &database.sql.clan_members_lookup,
+                vec![
+                    Fixture::result(&[
+                        ("uid", Value::from("76561198037177305")),
+                        ("name", Value::from("Bryan")),
+                    ]),
+                    Fixture::result(&[
+                        ("uid", Value::from("76561198025434405")),
+                        ("name", Value::from("Andrew")),
+                    ]),
+                ],
+            )
+            .respond_with(
+                &database.sql.clan_territories_lookup,
+                vec![Fixture::result(&[
+                    ("id", Value::from("1")),
+                    ("name", Value::from("Home")),
+                    ("owner_uid", Value::from("76561198025434405")),
+                ])],
+            );
+
+        let arguments = HashMap::from([("clan_id".to_string(), "3".to_string())]);
+
+        let results = command_clan_info(&database, &mut fake, &arguments)
+            .await
+            .unwrap();
+
+        let clan: JSONValue = serde_json::from_str(&results[0]).unwrap();
+
+        assert_eq!(clan["leader_name"], json!("Bryan"));
+        assert_eq!(clan["members"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            clan["territories"][0]["id"],
+            json!(database.encode_territory_id("1"))
+        );
+    }
+
+    #[tokio::test]
+    async fn it_returns_nothing_for_unknown_clans() {
+        let database = Fixture::database();
+        let mut fake = FakeExecutor::new()
+            .respond_with(&database.sql.command_clan_info, vec![]);
+
+        let arguments = HashMap::from([("clan_id".to_string(), "3".to_string())]);
+
+        let results = command_clan_info(&database, &mut fake, &arguments)
+            .await
+            .unwrap();
+
+        assert!(results.is_empty());
+    }
+}
@@ -0,0 +1,221 @@
+use super::*;
+
+/// Flags the territory, its constructions, and its containers as deleted so command_restore
+/// can bring them back, and records who deleted it and why
+/// Arguments:
+///     territory_id: The territory's encoded or custom ID
+///     steam_uid: The player deleting the territory. Must own the territory unless they are
+///         a territory admin
+///     reason: Optional
+pub async fn command_delete_territory(
+    context: &Database,
+    connection: &mut dyn Executor,
+    arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let Some(territory_id) = arguments.get("territory_id") else {
+        return Err(QueryError::User(
+            "Missing key `territory_id` in provided query arguments".into(),
+        ));
+    };
+
+    let Some(steam_uid) = arguments.get("steam_uid") else {
+        return Err(QueryError::User(
+            "Missing key `steam_uid` in provided query arguments".into(),
+        ));
+    };
+
+    let reason = arguments.get("reason").filter(|r| !r.trim().is_empty());
+
+    // This handles both hashed IDs or custom IDs
+    let territory_id =
+        queries::decode_territory_id(context, connection, territory_id).await?;
+
+    // Territory admins can delete any territory. Everyone else must own it
+    if !arma::is_territory_admin(steam_uid) {
+        let is_owner = queries::check_if_territory_owner(
+            context,
+            connection,
+            territory_id,
+            steam_uid,
+        )
+        .await?;
+
+        if !is_owner {
+            // Pretending the territory doesn't exist keeps from exposing which IDs do
+            return Err(QueryError::Code("territory_id_does_not_exist".into()));
+        }
+    }
+
+    // Mirrors command_restore. The territory is checked first so nothing is touched if it
+    // has already been deleted
+    let deleted = connection
+        .execute(
+            &context.sql.command_delete_territory,
+            params! { territory_id },
+        )
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    if deleted == 0 {
+        return Err(QueryError::User(
+            "This territory has already been deleted".into(),
+        ));
+    }
+
+    for statement in [
+        &context.sql.command_delete_construction,
+        &context.sql.command_delete_container,
+    ] {
+        connection
+            .exec_drop(statement, params! { territory_id })
+            .await
+            .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+    }
+
+    let result = connection
+        .exec_drop(
+            &context.sql.command_delete_territory_record,
+            params! {
+                "territory_id" => territory_id,
+                "deleted_by_uid" => steam_uid,
+                "reason" => reason
+            },
+        )
+        .await;
+
+    match result {
+        Ok(_) => Ok(vec![]),
+        Err(e) => Err(QueryError::System(format!("Query failed - {}", e))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+    use mysql_async::Value;
+
+    fn arguments(database: &Database, steam_uid: &str) -> HashMap<String, String> {
+        HashMap::from([
+            (
+                "territory_id".to_string(),
+                database.encode_territory_id("5"),
+            ),
+            ("steam_uid".to_string(), steam_uid.to_string()),
+        ])
+    }
+
+    fn fake(database: &Database, is_owner: &str) -> FakeExecutor {
+        FakeExecutor::new()
+            .respond_with(
+                &database.sql.check_if_territory_exists,
+                vec![Fixture::result(&[("exists", Value::from("true"))])],
+            )
+            .respond_with(
+                &database.sql.check_if_territory_owner,
+                vec![Fixture::result(&[("is_owner", Value::from(is_owner))])],
+            )
+    }
+
+    #[tokio::test]
+    async fn it_hides_territories_the_player_does_not_own() {
+        let database = Fixture::database();
+        let mut fake = fake(&database, "false");
+
+        let result = command_delete_territory(
+            &database,
+            &mut fake,
+            &arguments(&database, "76561198000000001"),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(QueryError::Code(e)) if e == "territory_id_does_not_exist"
+        ));
+        assert_eq!(
+            fake.statements,
+            vec![
+                database.sql.check_if_territory_exists.to_owned(),
+                database.sql.check_if_territory_owner.to_owned(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_deletes_the_territory_for_its_owner() {
+        let database = Fixture::database();
+        let mut fake = fake(&database, "true")
+            .affect(&database.sql.command_delete_territory, 1)
+            .affect(&database.sql.command_delete_construction, 12)
+            .affect(&database.sql.command_delete_container, 3)
+            .affect(&database.sql.command_delete_territory_record, 1);
+
+        let result = command_delete_territory(
+            &database,
+            &mut fake,
+            &arguments(&database, "76561198000000002"),
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(
+            fake.statements,
+            vec![
+                database.sql.check_if_territory_exists.to_owned(),
+                database.sql.check_if_territory_owner.to_owned(),
+                database.sql.command_delete_territory.to_owned(),
+                database.sql.command_delete_construction.to_owned(),
+                database.sql.command_delete_container.to_owned(),
+                database.sql.command_delete_territory_record.to_owned(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_stops_if_the_territory_was_already_deleted() {
+        let database = Fixture::database();
+        let mut fake = fake(&database, "true")
+            .affect(&database.sql.command_delete_territory, 0);
+
+        let result = command_delete_territory(
+            &database,
+            &mut fake,
+            &arguments(&database, "76561198000000002"),
+        )
+        .await;
+
+        assert!(matches!(result, Err(QueryError::User(e)) if e.contains("already")));
+        assert_eq!(
+            fake.statements.last(),
+            Some(&database.sql.command_delete_territory)
+        );
+    }
+
+    #[tokio::test]
+    async fn it_lets_territory_admins_skip_the_owner_check() {
+        let database = Fixture::database();
+        let mut fake = fake(&database, "false");
+
+        arma::add_territory_admin("76561198000000003");
+
+        // Nothing was seeded for the delete, so it fails once authorization passes
+        let result = command_delete_territory(
+            &database,
+            &mut fake,
+            &arguments(&database, "76561198000000003"),
+        )
+        .await;
+
+        assert!(
+            matches!(result, Err(QueryError::System(e)) if e.contains("read-only"))
+        );
+        assert_eq!(
+            fake.statements,
+            vec![
+                database.sql.check_if_territory_exists.to_owned(),
+                database.sql.command_delete_territory.to_owned(),
+            ]
+        );
+    }
+}
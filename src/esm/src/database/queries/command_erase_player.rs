@@ -0,0 +1,337 @@
+use super::*;
+
+use std::collections::BTreeMap;
+
+#[derive(Debug, Serialize)]
+struct ErasureSummary {
+    uid: String,
+    dry_run: bool,
+    deleted: BTreeMap<&'static str, u64>,
+    anonymized: BTreeMap<&'static str, u64>,
+}
+
+/// Deletes everything keyed by the player's Steam UID. Rows that belong to someone else's
+/// territory are anonymized instead so other players don't lose their bases.
+/// An erasure must run inside of a transaction. A dry run only counts the rows and writes nothing
+/// Arguments:
+///     uid: The player's Steam UID
+///     dry_run: "true" or "false". Defaults to true so nothing is erased by accident
+pub async fn command_erase_player(
+    context: &Database,
+    connection: &mut dyn Executor,
+    arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let Some(uid) = arguments.get("uid") else {
+        return Err(QueryError::User(
+            "Missing key `uid` in provided query arguments".into(),
+        ));
+    };
+
+    let dry_run = dry_run(arguments)?;
+
+    if !queries::check_if_account_exists(context, connection, uid).await? {
+        return Err(QueryError::User(format!("{uid} has not joined the server")));
+    }
+
+    let mut summary = ErasureSummary {
+        uid: uid.to_owned(),
+        dry_run,
+        deleted: BTreeMap::new(),
+        anonymized: BTreeMap::new(),
+    };
+
+    summary.anonymized.insert(
+        "territory_rights",
+        remove_from_rights(context, connection, uid, dry_run).await?,
+    );
+
+    if dry_run {
+        count(context, connection, uid, &mut summary).await?;
+        return Ok(vec![serde_json::to_string(&summary).unwrap()]);
+    }
+
+    // The order matters. Anything shared is handed off before the player's own rows are
+    // deleted, otherwise the account's foreign keys would cascade into other players' data
+    let anonymize = vec![
+        ("clan_leadership", &context.sql.erase_player_clan_leadership),
+        (
+            "constructions",
+            &context.sql.erase_player_shared_constructions,
+        ),
+        ("containers", &context.sql.erase_player_shared_containers),
+    ];
+
+    for (name, statement) in anonymize {
+        let count = execute(connection, statement, uid).await?;
+        summary.anonymized.insert(name, count);
+    }
+
+    // Rows that the account's foreign keys would cascade to are deleted explicitly so
+    // they are counted
+    let delete = vec![
+        (
+            "xm8_notifications",
+            &context.sql.erase_player_xm8_notifications,
+        ),
+        ("xm8_preferences", &context.sql.erase_player_xm8_preferences),
+        ("xm8_quiet_hours", &context.sql.erase_player_xm8_quiet_hours),
+        (
+            "xm8_suppressed_notifications",
+            &context.sql.erase_player_xm8_suppressed_notifications,
+        ),
+        ("sessions", &context.sql.erase_player_sessions),
+        ("player_history", &context.sql.erase_player_history),
+        ("player", &context.sql.erase_player_player),
+        ("vehicles", &context.sql.erase_player_vehicles),
+        ("territories", &context.sql.erase_player_territories),
+        ("constructions", &context.sql.erase_player_constructions),
+        ("containers", &context.sql.erase_player_containers),
+        ("clans", &context.sql.erase_player_clans),
+        ("account", &context.sql.erase_player_account),
+    ];
+
+    for (name, statement) in delete {
+        let count = execute(connection, statement, uid).await?;
+        summary.deleted.insert(name, count);
+    }
+
+    Ok(vec![serde_json::to_string(&summary).unwrap()])
+}
+
+/// Used by command_erase_player and Database::command_erase_player to decide whether to write
+pub fn dry_run(arguments: &HashMap<String, String>) -> Result<bool, QueryError> {
+    match arguments.get("dry_run").map(|d| d.as_str()) {
+        None | Some("true") => Ok(true),
+        Some("false") => Ok(false),
+        Some(value) => Err(QueryError::User(format!(
+            "`dry_run` must be \"true\" or \"false\", got \"{value}\""
+        ))),
+    }
+}
+
+async fn execute(
+    connection: &mut dyn Executor,
+    statement: &str,
+    uid: &str,
+) -> Result<u64, QueryError> {
+    connection
+        .execute(statement, params! { "uid" => uid })
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))
+}
+
+/// Counts what the erasure would anonymize and delete, in the same order it runs. Constructions
+/// and containers in the player's own territories are removed along with the territory by
+/// Exile's foreign keys, so only the ones outside of any territory are counted
+async fn count(
+    context: &Database,
+    connection: &mut dyn Executor,
+    uid: &str,
+    summary: &mut ErasureSummary,
+) -> Result<(), QueryError> {
+    let row: Option<Row> = connection
+        .exec_first(&context.sql.erase_player_counts, params! { "uid" => uid })
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    let Some(row) = row else {
+        return Err(QueryError::System(
+            "Query failed - erase_player_counts returned nothing".into(),
+        ));
+    };
+
+    let column = |name: &str| -> Result<u64, QueryError> {
+        select_column(&row, name)
+            .map_err(|e| QueryError::System(format!("Query failed - {e}")))
+    };
+
+    for (name, column_name) in [
+        ("clan_leadership", "clan_leadership"),
+        ("constructions", "shared_constructions"),
+        ("containers", "shared_containers"),
+    ] {
+        summary.anonymized.insert(name, column(column_name)?);
+    }
+
+    for name in [
+        "xm8_notifications",
+        "xm8_preferences",
+        "xm8_quiet_hours",
+        "xm8_suppressed_notifications",
+        "sessions",
+        "player_history",
+        "player",
+        "vehicles",
+        "territories",
+        "constructions",
+        "containers",
+        "clans",
+        "account",
+    ] {
+        summary.deleted.insert(name, column(name)?);
+    }
+
+    Ok(())
+}
+
+/// Removes the player from the build rights and moderators of territories they don't own.
+/// A dry run only counts the territories
+async fn remove_from_rights(
+    context: &Database,
+    connection: &mut dyn Executor,
+    uid: &str,
+    dry_run: bool,
+) -> Result<u64, QueryError> {
+    let territories: Vec<(u64, String, String)> = connection
+        .exec_map(
+            &context.sql.erase_player_rights_lookup,
+            params! {
+                "uid" => uid,
+                "wildcard_uid" => format!("%{uid}%")
+            },
+            |row| row,
+        )
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    if dry_run {
+        return Ok(territories.len() as u64);
+    }
+
+    let mut count = 0;
+    for (territory_id, build_rights, moderators) in territories {
+        connection
+            .exec_drop(
+                &context.sql.erase_player_rights,
+                params! {
+                    "territory_id" => territory_id,
+                    "build_rights" => remove_rights(&build_rights, uid)?,
+                    "moderators" => remove_rights(&moderators, uid)?
+                },
+            )
+            .await
+            .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+fn remove_rights(rights: &str, uid: &str) -> Result<String, QueryError> {
+    let rights: Vec<String> = serde_json::from_str(rights).map_err(|e| {
+        QueryError::System(format!(
+            "Failed to parse territory rights {rights:?} - {e}"
+        ))
+    })?;
+
+    let rights: Vec<String> = rights.into_iter().filter(|r| r != uid).collect();
+
+    Ok(serde_json::to_string(&rights).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+
+    #[test]
+    fn it_defaults_to_a_dry_run() {
+        assert!(dry_run(&HashMap::new()).unwrap());
+
+        let arguments =
+            HashMap::from([("dry_run".to_string(), "false".to_string())]);
+        assert!(!dry_run(&arguments).unwrap());
+
+        let arguments = HashMap::from([("dry_run".to_string(), "no".to_string())]);
+        assert!(dry_run(&arguments).is_err());
+    }
+
+    #[test]
+    fn it_removes_the_player_from_rights() {
+        let rights = r#"["76561198037177305","76561198025434405"]"#;
+
+        let result = remove_rights(rights, "76561198025434405").unwrap();
+
+        assert_eq!(result, r#"["76561198037177305"]"#);
+    }
+
+    #[tokio::test]
+    async fn it_requires_a_uid() {
+        let database = Fixture::database();
+        let mut fake = FakeExecutor::new();
+
+        let result =
+            command_erase_player(&database, &mut fake, &HashMap::new()).await;
+
+        assert!(matches!(result, Err(QueryError::User(_))));
+        assert!(fake.statements.is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_only_counts_on_a_dry_run() {
+        let database = Fixture::database();
+
+        let columns = [
+            "clan_leadership",
+            "shared_constructions",
+            "shared_containers",
+            "xm8_notifications",
+            "xm8_preferences",
+            "xm8_quiet_hours",
+            "xm8_suppressed_notifications",
+            "sessions",
+            "player_history",
+            "player",
+            "vehicles",
+            "territories",
+            "constructions",
+            "containers",
+            "clans",
+            "account",
+        ]
+        .map(|column| (column, Value::UInt(1)));
+
+        let mut fake = FakeExecutor::new()
+            .respond_with(
+                &database.sql.check_if_account_exists,
+                vec![Fixture::result(&[("exists", Value::from("true"))])],
+            )
+            .respond_with(
+                &database.sql.erase_player_rights_lookup,
+                vec![Fixture::result(&[
+                    ("id", Value::UInt(1)),
+                    ("build_rights", Value::from(r#"["76561198025434405"]"#)),
+                    ("moderators", Value::from("[]")),
+                ])],
+            )
+            .respond_with(
+                &database.sql.erase_player_counts,
+                vec![Fixture::result(&columns)],
+            );
+
+        let arguments =
+            HashMap::from([("uid".to_string(), "76561198025434405".to_string())]);
+
+        let results = command_erase_player(&database, &mut fake, &arguments)
+            .await
+            .unwrap();
+
+        let summary: JSONValue = serde_json::from_str(&results[0]).unwrap();
+
+        assert_eq!(summary["dry_run"], json!(true));
+        assert_eq!(summary["anonymized"]["territory_rights"], json!(1));
+        assert_eq!(summary["anonymized"]["constructions"], json!(1));
+        assert_eq!(summary["deleted"]["account"], json!(1));
+        assert_eq!(summary["deleted"].as_object().unwrap().len(), 13);
+
+        assert_eq!(
+            fake.statements,
+            vec![
+                database.sql.check_if_account_exists.to_owned(),
+                database.sql.erase_player_rights_lookup.to_owned(),
+                database.sql.erase_player_counts.to_owned(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use super::*;
+
+/// Collects every row keyed by the player's Steam UID into a single JSON document
+/// Arguments:
+///     uid: The player's Steam UID
+pub async fn command_export_player(
+    context: &Database,
+    connection: &mut dyn Executor,
+    arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let Some(uid) = arguments.get("uid") else {
+        return Err(QueryError::User(
+            "Missing key `uid` in provided query arguments".into(),
+        ));
+    };
+
+    if !queries::check_if_account_exists(context, connection, uid).await? {
+        return Err(QueryError::User(format!("{uid} has not joined the server")));
+    }
+
+    let sections = vec![
+        ("account", &context.sql.export_player_account),
+        ("player", &context.sql.export_player_player),
+        ("player_history", &context.sql.export_player_history),
+        ("sessions", &context.sql.export_player_sessions),
+        ("territories", &context.sql.export_player_territories),
+        ("constructions", &context.sql.export_player_constructions),
+        ("containers", &context.sql.export_player_containers),
+        ("vehicles", &context.sql.export_player_vehicles),
+        (
+            "xm8_notifications",
+            &context.sql.export_player_xm8_notifications,
+        ),
+        (
+            "xm8_preferences",
+            &context.sql.export_player_xm8_preferences,
+        ),
+        (
+            "xm8_quiet_hours",
+            &context.sql.export_player_xm8_quiet_hours,
+        ),
+        (
+            "xm8_suppressed_notifications",
+            &context.sql.export_player_xm8_suppressed_notifications,
+        ),
+    ];
+
+    let mut export = serde_json::Map::new();
+    export.insert("uid".into(), json!(uid));
+    export.insert("exported_at".into(), json!(Utc::now()));
+
+    for (section, statement) in sections {
+        let rows = connection
+            .fetch(
+                statement,
+                params! {
+                    "uid" => uid,
+                    "wildcard_uid" => format!("%{uid}%")
+                },
+            )
+            .await
+            .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+        let rows: Vec<JSONValue> = rows.into_iter().map(row_to_json).collect();
+
+        // There is only ever one account
+        let value = if section == "account" {
+            rows.into_iter().next().unwrap_or(JSONValue::Null)
+        } else {
+            JSONValue::Array(rows)
+        };
+
+        export.insert(section.into(), value);
+    }
+
+    Ok(vec![JSONValue::Object(export).to_string()])
+}
+
+fn row_to_json(row: Row) -> JSONValue {
+    let columns = row.columns();
+
+    let object = columns
+        .iter()
+        .zip(row.unwrap())
+        .map(|(column, value)| (column.name_str().to_string(), json_value(&value)))
+        .collect();
+
+    JSONValue::Object(object)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+    use mysql_async::Value;
+
+    #[tokio::test]
+    async fn it_exports_every_section() {
+        let database = Fixture::database();
+        let fixture = Fixture::load();
+
+        let mut fake = FakeExecutor::new();
+        for statement in vec![
+            &database.sql.export_player_player,
+            &database.sql.export_player_history,
+            &database.sql.export_player_sessions,
+            &database.sql.export_player_territories,
+            &database.sql.export_player_constructions,
+            &database.sql.export_player_containers,
+            &database.sql.export_player_xm8_notifications,
+            &database.sql.export_player_xm8_preferences,
+            &database.sql.export_player_xm8_suppressed_notifications,
+        ] {
+            fake = fake.respond_with(statement, vec![]);
+        }
+
+        let mut fake = fake
+            .respond_with(
+                &database.sql.check_if_account_exists,
+                vec![Fixture::result(&[("exists", Value::from("true"))])],
+            )
+            .respond_with(
+                &database.sql.export_player_account,
+                vec![fixture.row(
+                    "account",
+                    &[
+                        ("uid", Value::from("76561198037177305")),
+                        ("name", Value::from("Bryan")),
+                        ("locker", Value::Int(5000)),
+                    ],
+                )],
+            )
+            .respond_with(
+                &database.sql.export_player_xm8_quiet_hours,
+                vec![fixture.row(
+                    "esm_xm8_quiet_hours",
+                    &[
+                        ("recipient_uid", Value::from("76561198037177305")),
+                        ("starts_at", Value::Time(false, 0, 22, 0, 0, 0)),
+                        ("ends_at", Value::Time(false, 0, 7, 30, 0, 0)),
+                    ],
+                )],
+            )
+            .respond_with(
+                &database.sql.export_player_vehicles,
+                vec![fixture.row(
+                    "vehicle",
+                    &[
+                        ("id", Value::UInt(12)),
+                        ("account_uid", Value::from("76561198037177305")),
+                    ],
+                )],
+            );
+
+        let arguments =
+            HashMap::from([("uid".to_string(), "76561198037177305".to_string())]);
+
+        let results = command_export_player(&database, &mut fake, &arguments)
+            .await
+            .unwrap();
+
+        let export: JSONValue = serde_json::from_str(&results[0]).unwrap();
+
+        assert_eq!(export["account"]["name"], json!("Bryan"));
+        assert_eq!(export["account"]["locker"], json!(5000));
+        assert_eq!(export["vehicles"][0]["id"], json!(12));
+        assert_eq!(export["territories"], json!([]));
+        assert_eq!(export["xm8_notifications"], json!([]));
+        assert_eq!(export["xm8_quiet_hours"][0]["starts_at"], json!("22:00:00"));
+        assert_eq!(export["xm8_quiet_hours"][0]["ends_at"], json!("07:30:00"));
+    }
+}
@@ -0,0 +1,261 @@
+use super::*;
+
+const DEFAULT_LIMIT: usize = 5;
+const MAX_LIMIT: usize = 25;
+
+// Names less similar than this are not considered a match
+const MIN_SIMILARITY: f64 = 0.5;
+
+// How far a mistyped name's length can be from the search's length
+const LENGTH_TOLERANCE: usize = 2;
+
+// The most accounts the database hands over for ranking. Recently active players come first
+const MAX_CANDIDATES: usize = 500;
+
+#[derive(Debug, Serialize)]
+struct Candidate {
+    uid: String,
+    name: String,
+    last_connect_at: NaiveDateTime,
+    #[serde(rename = "match")]
+    match_type: MatchType,
+    score: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum MatchType {
+    Uid,
+    Exact,
+    Prefix,
+    Contains,
+    Similar,
+}
+
+/// Finds players by a partial or mistyped name, or the start of their Steam UID
+/// Arguments:
+///     name: What to search for. At least 2 characters
+///     limit: 1 to 25, defaults to 5
+pub async fn command_find_player(
+    context: &Database,
+    connection: &mut dyn Executor,
+    arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let Some(search) = arguments.get("name") else {
+        return Err(QueryError::User(
+            "Missing key `name` in provided query arguments".into(),
+        ));
+    };
+
+    let search = search.trim().to_lowercase();
+    let search_length = search.chars().count();
+
+    if search_length < 2 {
+        return Err(QueryError::User(
+            "`name` must be at least 2 characters".into(),
+        ));
+    }
+
+    let limit = match arguments.get("limit") {
+        Some(limit) => match limit.parse::<usize>() {
+            Ok(limit) if (1..=MAX_LIMIT).contains(&limit) => limit,
+            _ => {
+                return Err(QueryError::User(format!(
+                    "`limit` must be a number between 1 and {MAX_LIMIT}"
+                )))
+            }
+        },
+        None => DEFAULT_LIMIT,
+    };
+
+    // Only searches that look like a Steam UID are matched against UIDs
+    let uid_prefix = if search.chars().all(|c| c.is_ascii_digit()) {
+        Some(format!("{search}%"))
+    } else {
+        None
+    };
+
+    // Mistyped names of a similar length still have to start with the same character
+    let first_character: String = search.chars().take(1).collect();
+
+    // The database only narrows the accounts down. The ranking happens below
+    let accounts: Vec<(String, String, NaiveDateTime)> = connection
+        .exec_map(
+            &context.sql.command_find_player,
+            params! {
+                "uid_prefix" => uid_prefix,
+                "wildcard_name" => format!("%{}%", escape_like(&search)),
+                "name" => &search,
+                "first_character" => format!("{}%", escape_like(&first_character)),
+                "min_length" => search_length.saturating_sub(LENGTH_TOLERANCE),
+                "max_length" => search_length + LENGTH_TOLERANCE,
+                "max_candidates" => MAX_CANDIDATES
+            },
+            |row| row,
+        )
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    let mut candidates: Vec<Candidate> = accounts
+        .into_iter()
+        .filter_map(|(uid, name, last_connect_at)| {
+            let (match_type, score) = rank(&search, &uid, &name)?;
+
+            Some(Candidate {
+                uid,
+                name,
+                last_connect_at,
+                match_type,
+                score: (score * 100.0).round() / 100.0,
+            })
+        })
+        .collect();
+
+    // Best match first. Recently active players win ties
+    candidates.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| b.last_connect_at.cmp(&a.last_connect_at))
+    });
+
+    Ok(candidates
+        .iter()
+        .take(limit)
+        .filter_map(|candidate| serde_json::to_string(candidate).ok())
+        .collect())
+}
+
+/// Scores how well the account matches the search, from 0 to 1
+/// The search must already be lowercase
+fn rank(search: &str, uid: &str, name: &str) -> Option<(MatchType, f64)> {
+    if uid.starts_with(search) {
+        let score = if uid == search { 1.0 } else { 0.9 };
+        return Some((MatchType::Uid, score));
+    }
+
+    let name = name.to_lowercase();
+
+    if name == search {
+        return Some((MatchType::Exact, 1.0));
+    }
+
+    if name.starts_with(search) {
+        return Some((MatchType::Prefix, 0.9));
+    }
+
+    if name.contains(search) {
+        return Some((MatchType::Contains, 0.8));
+    }
+
+    let score = similarity(search, &name);
+    if score < MIN_SIMILARITY {
+        return None;
+    }
+
+    // A similar name should never outrank a name containing the search
+    Some((MatchType::Similar, score * 0.75))
+}
+
+/// 1 minus the Levenshtein distance relative to the longer string
+fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.iter().enumerate() {
+        current[0] = i + 1;
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] =
+                substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+fn escape_like(input: &str) -> String {
+    input
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+    use mysql_async::Value;
+
+    #[test]
+    fn it_ranks_matches() {
+        let uid = "76561198037177305";
+
+        assert_eq!(
+            rank("7656119803", uid, "Bryan"),
+            Some((MatchType::Uid, 0.9))
+        );
+        assert_eq!(rank("bryan", uid, "Bryan"), Some((MatchType::Exact, 1.0)));
+        assert_eq!(rank("bry", uid, "Bryan"), Some((MatchType::Prefix, 0.9)));
+        assert_eq!(rank("rya", uid, "Bryan"), Some((MatchType::Contains, 0.8)));
+        assert_eq!(
+            rank("brain", uid, "Bryan").map(|(m, _)| m),
+            Some(MatchType::Similar)
+        );
+        assert_eq!(rank("andrew", uid, "Bryan"), None);
+    }
+
+    #[test]
+    fn it_calculates_similarity() {
+        assert_eq!(similarity("bryan", "bryan"), 1.0);
+        assert!((similarity("bryan", "brian") - 0.8).abs() < f64::EPSILON);
+        assert_eq!(similarity("", ""), 1.0);
+    }
+
+    #[tokio::test]
+    async fn it_returns_the_best_candidates_first() {
+        let database = Fixture::database();
+
+        let account = |uid: &str, name: &str, day: u8| {
+            Fixture::result(&[
+                ("uid", Value::from(uid)),
+                ("name", Value::from(name)),
+                ("last_connect_at", Value::Date(2024, 12, day, 0, 0, 0, 0)),
+            ])
+        };
+
+        let mut fake = FakeExecutor::new().respond_with(
+            &database.sql.command_find_player,
+            vec![
+                account("76561198025434405", "Brain", 20),
+                account("76561198037177305", "Bryan", 1),
+                account("76561198018283626", "Bryant", 20),
+                account("76561198000000000", "Andrew", 20),
+            ],
+        );
+
+        let arguments = HashMap::from([("name".to_string(), "BRYAN".to_string())]);
+
+        let results = command_find_player(&database, &mut fake, &arguments)
+            .await
+            .unwrap();
+
+        let names: Vec<String> = results
+            .iter()
+            .map(|r| serde_json::from_str::<JSONValue>(r).unwrap())
+            .map(|r| r["name"].as_str().unwrap().to_string())
+            .collect();
+
+        assert_eq!(names, vec!["Bryan", "Bryant", "Brain"]);
+    }
+}
@@ -0,0 +1,159 @@
+use super::*;
+
+/// The kinds of leftover rows Exile accumulates. Checked by command_integrity_check and
+/// removed by command_integrity_cleanup
+pub const INTEGRITY_CATEGORIES: &[&str] = &[
+    "orphaned_constructions",
+    "orphaned_containers",
+    "orphaned_xm8_notifications",
+    "dead_players",
+];
+
+#[derive(Debug, Serialize)]
+struct CategoryReport {
+    category: String,
+    count: u64,
+    sample_ids: Vec<u64>,
+}
+
+/// Reports how many rows fall into each integrity category along with a few of their IDs
+pub async fn command_integrity_check(
+    context: &Database,
+    connection: &mut dyn Executor,
+    _arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let mut results = vec![];
+
+    for category in INTEGRITY_CATEGORIES {
+        let (count, sample_ids) =
+            check_category(context, connection, category).await?;
+
+        let report = CategoryReport {
+            category: category.to_string(),
+            count,
+            sample_ids,
+        };
+
+        results.push(serde_json::to_string(&report).unwrap());
+    }
+
+    Ok(results)
+}
+
+/// Returns the number of rows in the category and up to 10 of their IDs
+pub async fn check_category(
+    context: &Database,
+    connection: &mut dyn Executor,
+    category: &str,
+) -> Result<(u64, Vec<u64>), QueryError> {
+    let (statement, _) = integrity_statements(context, category)?;
+
+    let result: Option<(u64, Option<String>)> = connection
+        .exec_first(statement, Params::Empty)
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    let (count, sample_ids) = result.unwrap_or_default();
+
+    let sample_ids = sample_ids
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|id| id.parse().ok())
+        .collect();
+
+    Ok((count, sample_ids))
+}
+
+/// The check statement for the category and the statements that clean it up
+pub fn integrity_statements<'a>(
+    context: &'a Database,
+    category: &str,
+) -> Result<(&'a str, Vec<&'a str>), QueryError> {
+    let sql = &context.sql;
+
+    // Territories that are only flagged as deleted can still be restored, so their
+    // constructions and containers are flagged too instead of being removed
+    let statements = match category {
+        "orphaned_constructions" => (
+            &sql.integrity_orphaned_constructions,
+            vec![
+                &sql.cleanup_orphaned_constructions,
+                &sql.cleanup_deleted_territory_constructions,
+            ],
+        ),
+        "orphaned_containers" => (
+            &sql.integrity_orphaned_containers,
+            vec![
+                &sql.cleanup_orphaned_containers,
+                &sql.cleanup_deleted_territory_containers,
+            ],
+        ),
+        "orphaned_xm8_notifications" => (
+            &sql.integrity_orphaned_xm8_notifications,
+            vec![&sql.cleanup_orphaned_xm8_notifications],
+        ),
+        // command_reset_all already removes dead players
+        "dead_players" => {
+            (&sql.integrity_dead_players, vec![&sql.command_reset_all])
+        }
+        category => {
+            return Err(QueryError::User(format!(
+                "\"{category}\" is not a valid category. Expected one of: {}",
+                INTEGRITY_CATEGORIES.join(", ")
+            )))
+        }
+    };
+
+    Ok((
+        statements.0.as_str(),
+        statements.1.into_iter().map(String::as_str).collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+    use mysql_async::Value;
+
+    #[tokio::test]
+    async fn it_reports_every_category() {
+        let database = Fixture::database();
+
+        let mut fake = FakeExecutor::new();
+        for category in INTEGRITY_CATEGORIES {
+            let (statement, _) = integrity_statements(&database, category).unwrap();
+
+            fake = fake.respond_with(
+                statement,
+                vec![Fixture::result(&[
+                    ("count", Value::Int(0)),
+                    ("sample_ids", Value::NULL),
+                ])],
+            );
+        }
+
+        let mut fake = fake.respond_with(
+            &database.sql.integrity_orphaned_containers,
+            vec![Fixture::result(&[
+                ("count", Value::Int(12)),
+                ("sample_ids", Value::from("4,8,15")),
+            ])],
+        );
+
+        let results = command_integrity_check(&database, &mut fake, &HashMap::new())
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), INTEGRITY_CATEGORIES.len());
+
+        let containers: JSONValue = serde_json::from_str(&results[1]).unwrap();
+
+        assert_eq!(containers["category"], json!("orphaned_containers"));
+        assert_eq!(containers["count"], json!(12));
+        assert_eq!(containers["sample_ids"], json!([4, 8, 15]));
+
+        let players: JSONValue = serde_json::from_str(&results[3]).unwrap();
+        assert_eq!(players["sample_ids"], json!([]));
+    }
+}
@@ -0,0 +1,130 @@
+use super::*;
+
+use std::collections::BTreeMap;
+
+#[derive(Debug, Serialize)]
+struct CleanupResult {
+    dry_run: bool,
+    removed: BTreeMap<String, u64>,
+}
+
+/// Removes the rows reported by command_integrity_check. Nothing is removed unless its
+/// category is asked for. Constructions and containers of territories that are flagged as
+/// deleted are flagged as well so restoring the territory brings them back
+/// Arguments:
+///     categories: Comma separated list of the categories to clean up
+///     dry_run: "true" or "false". Defaults to true, which reports what would be removed
+pub async fn command_integrity_cleanup(
+    context: &Database,
+    connection: &mut dyn Executor,
+    arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let Some(categories) = arguments.get("categories") else {
+        return Err(QueryError::User(
+            "Missing key `categories` in provided query arguments".into(),
+        ));
+    };
+
+    let dry_run = queries::dry_run(arguments)?;
+
+    let categories: Vec<&str> = categories
+        .split(',')
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .collect();
+
+    if categories.is_empty() {
+        return Err(QueryError::User(format!(
+            "`categories` must contain at least one of: {}",
+            INTEGRITY_CATEGORIES.join(", ")
+        )));
+    }
+
+    // Validate everything before touching anything
+    for category in &categories {
+        queries::integrity_statements(context, category)?;
+    }
+
+    let mut removed = BTreeMap::new();
+    for category in categories {
+        let count = if dry_run {
+            let (count, _) =
+                queries::check_category(context, connection, category).await?;
+
+            count
+        } else {
+            let (_, statements) = queries::integrity_statements(context, category)?;
+
+            let mut count = 0;
+            for statement in statements {
+                count +=
+                    connection.execute(statement, Params::Empty).await.map_err(
+                        |e| QueryError::System(format!("Query failed - {}", e)),
+                    )?;
+            }
+
+            count
+        };
+
+        removed.insert(category.to_string(), count);
+    }
+
+    let result = CleanupResult { dry_run, removed };
+
+    Ok(vec![serde_json::to_string(&result).unwrap()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+    use mysql_async::Value;
+
+    #[tokio::test]
+    async fn it_only_previews_by_default() {
+        let database = Fixture::database();
+
+        let mut fake = FakeExecutor::new().respond_with(
+            &database.sql.integrity_dead_players,
+            vec![Fixture::result(&[
+                ("count", Value::Int(3)),
+                ("sample_ids", Value::from("1,2,3")),
+            ])],
+        );
+
+        let arguments =
+            HashMap::from([("categories".to_string(), "dead_players".to_string())]);
+
+        let results = command_integrity_cleanup(&database, &mut fake, &arguments)
+            .await
+            .unwrap();
+
+        let result: JSONValue = serde_json::from_str(&results[0]).unwrap();
+
+        assert_eq!(result["dry_run"], json!(true));
+        assert_eq!(result["removed"]["dead_players"], json!(3));
+        assert_eq!(
+            fake.statements,
+            vec![database.sql.integrity_dead_players.clone()]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_rejects_unknown_categories() {
+        let database = Fixture::database();
+        let mut fake = FakeExecutor::new();
+
+        let arguments = HashMap::from([(
+            "categories".to_string(),
+            "dead_players, everything".to_string(),
+        )]);
+
+        let result =
+            command_integrity_cleanup(&database, &mut fake, &arguments).await;
+
+        assert!(
+            matches!(result, Err(QueryError::User(e)) if e.contains("not a valid category"))
+        );
+        assert!(fake.statements.is_empty());
+    }
+}
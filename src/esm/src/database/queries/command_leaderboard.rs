@@ -0,0 +1,213 @@
+use super::*;
+
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 100;
+
+// These are the column aliases in command_leaderboard.sql. They are inserted into the query
+// as is, so only these are allowed
+const STATS: &[&str] = &[
+    "kills",
+    "deaths",
+    "kd_ratio",
+    "score",
+    "locker",
+    "money",
+    "territory_count",
+];
+
+#[derive(Debug, Serialize)]
+struct PlayerResult {
+    rank: usize,
+    uid: String,
+    name: String,
+    clan_name: Option<String>,
+    score: i32,
+    kills: i32,
+    deaths: i32,
+    kd_ratio: f64,
+    locker: i32,
+    money: i32,
+    territory_count: i32,
+}
+
+/// Ranks players by a stat, highest first
+/// Arguments (all optional):
+///     stat: kills, deaths, kd_ratio, score (respect), locker, money (carried by the
+///         player's current character), or territory_count. Defaults to kills
+///     limit: 1 to 100, defaults to 10
+///     offset: The number of players to skip, defaults to 0
+///     clan_id: Only players in this clan
+pub async fn command_leaderboard(
+    context: &Database,
+    connection: &mut dyn Executor,
+    arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let stat = match arguments.get("stat") {
+        Some(stat) if STATS.contains(&stat.as_str()) => stat.as_str(),
+        Some(stat) => {
+            return Err(QueryError::User(format!(
+                "`stat` must be one of: {}. Got \"{stat}\"",
+                STATS.join(", ")
+            )))
+        }
+        None => "kills",
+    };
+
+    let limit = match arguments.get("limit") {
+        Some(limit) => match limit.parse::<usize>() {
+            Ok(limit) if (1..=MAX_LIMIT).contains(&limit) => limit,
+            _ => {
+                return Err(QueryError::User(format!(
+                    "`limit` must be a number between 1 and {MAX_LIMIT}"
+                )))
+            }
+        },
+        None => DEFAULT_LIMIT,
+    };
+
+    let offset = match arguments.get("offset") {
+        Some(offset) => offset.parse::<usize>().map_err(|_| {
+            QueryError::User("`offset` must be a positive number".into())
+        })?,
+        None => 0,
+    };
+
+    let clan_id =
+        match arguments.get("clan_id") {
+            Some(clan_id) => Some(clan_id.parse::<u64>().map_err(|_| {
+                QueryError::User("`clan_id` must be a number".into())
+            })?),
+            None => None,
+        };
+
+    let query = context.sql.command_leaderboard.replace(":stat", stat);
+
+    let result = connection
+        .exec_map(
+            &query,
+            params! {
+                "clan_id" => clan_id,
+                "limit" => limit,
+                "offset" => offset
+            },
+            map_results,
+        )
+        .await;
+
+    match result {
+        Ok(players) => {
+            let players = players
+                .into_iter()
+                .collect::<Result<Vec<PlayerResult>, String>>()
+                .map_err(|e| QueryError::System(format!("Query failed - {e}")))?;
+
+            let results = players
+                .into_iter()
+                .enumerate()
+                .filter_map(|(index, mut player)| {
+                    player.rank = offset + index + 1;
+                    serde_json::to_string(&player).ok()
+                })
+                .collect();
+
+            Ok(results)
+        }
+        Err(e) => Err(QueryError::System(format!("Query failed - {}", e))),
+    }
+}
+
+fn map_results(row: Row) -> Result<PlayerResult, String> {
+    Ok(PlayerResult {
+        rank: 0,
+        uid: select_column(&row, "uid")?,
+        name: select_column(&row, "name")?,
+        clan_name: select_column(&row, "clan_name")?,
+        score: select_column(&row, "score")?,
+        kills: select_column(&row, "kills")?,
+        deaths: select_column(&row, "deaths")?,
+        kd_ratio: select_column(&row, "kd_ratio")?,
+        locker: select_column(&row, "locker")?,
+        money: select_column(&row, "money")?,
+        territory_count: select_column(&row, "territory_count")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+    use mysql_async::Value;
+
+    fn player(uid: &str, kills: i64) -> Row {
+        Fixture::result(&[
+            ("uid", Value::from(uid)),
+            ("name", Value::from("Bryan")),
+            ("clan_name", Value::NULL),
+            ("score", Value::Int(1000)),
+            ("kills", Value::Int(kills)),
+            ("deaths", Value::Int(4)),
+            (
+                "kd_ratio",
+                Value::from(format!("{:.2}", kills as f64 / 4.0)),
+            ),
+            ("locker", Value::Int(50_000)),
+            ("money", Value::Int(250)),
+            ("territory_count", Value::Int(2)),
+        ])
+    }
+
+    #[tokio::test]
+    async fn it_ranks_players_from_the_offset() {
+        let database = Fixture::database();
+        let query = database
+            .sql
+            .command_leaderboard
+            .replace(":stat", "kd_ratio");
+
+        let mut fake = FakeExecutor::new().respond_with(
+            &query,
+            vec![
+                player("76561198037177305", 10),
+                player("76561198025434405", 6),
+            ],
+        );
+
+        let arguments = HashMap::from([
+            ("stat".to_string(), "kd_ratio".to_string()),
+            ("offset".to_string(), "10".to_string()),
+        ]);
+
+        let results = command_leaderboard(&database, &mut fake, &arguments)
+            .await
+            .unwrap();
+
+        let first: JSONValue = serde_json::from_str(&results[0]).unwrap();
+        let second: JSONValue = serde_json::from_str(&results[1]).unwrap();
+
+        assert_eq!(first["rank"], json!(11));
+        assert_eq!(first["kd_ratio"], json!(2.5));
+        assert_eq!(second["rank"], json!(12));
+        assert_eq!(second["uid"], json!("76561198025434405"));
+        assert_eq!(second["money"], json!(250));
+    }
+
+    #[tokio::test]
+    async fn it_rejects_invalid_arguments() {
+        let database = Fixture::database();
+        let mut fake = FakeExecutor::new();
+
+        for (key, value) in [
+            ("stat", "money; DROP TABLE account"),
+            ("limit", "0"),
+            ("offset", "-1"),
+            ("clan_id", "clan"),
+        ] {
+            let arguments = HashMap::from([(key.to_string(), value.to_string())]);
+            let result = command_leaderboard(&database, &mut fake, &arguments).await;
+
+            assert!(matches!(result, Err(QueryError::User(_))), "{}", key);
+        }
+
+        assert!(fake.statements.is_empty());
+    }
+}
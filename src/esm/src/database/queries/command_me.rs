@@ -22,7 +22,7 @@ struct PlayerResult {
 
 pub async fn command_me(
     context: &Database,
-    connection: &mut Conn,
+    connection: &mut dyn Executor,
     arguments: &HashMap<String, String>,
 ) -> QueryResult {
     let player_uid = match arguments.get("uid") {
@@ -81,3 +81,58 @@ pub async fn command_me(
         Err(e) => Err(QueryError::System(format!("Query failed - {}", e))),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+    use mysql_async::Value;
+
+    #[tokio::test]
+    async fn it_returns_the_player_with_encoded_territory_ids() {
+        let database = Fixture::database();
+
+        let row = Fixture::result(&[
+            ("locker", Value::Int(1000)),
+            ("score", Value::Int(5)),
+            ("name", Value::from("Bryan")),
+            ("money", Value::NULL),
+            ("damage", Value::NULL),
+            ("hunger", Value::NULL),
+            ("thirst", Value::NULL),
+            ("kills", Value::Int(2)),
+            ("deaths", Value::Int(1)),
+            (
+                "territories",
+                Value::from(r#"[{"id": "1", "name": "Home"}]"#),
+            ),
+        ]);
+
+        let mut fake =
+            FakeExecutor::new().respond_with(&database.sql.command_me, vec![row]);
+
+        let arguments =
+            HashMap::from([("uid".to_string(), "76561198037177305".to_string())]);
+
+        let results = command_me(&database, &mut fake, &arguments).await.unwrap();
+        let player: JSONValue = serde_json::from_str(&results[0]).unwrap();
+
+        assert_eq!(player["name"], json!("Bryan"));
+        assert_eq!(player["money"], JSONValue::Null);
+        assert_eq!(
+            player["territories"][0]["id"],
+            json!(database.encode_territory_id("1"))
+        );
+    }
+
+    #[tokio::test]
+    async fn it_requires_a_uid() {
+        let database = Fixture::database();
+        let mut fake = FakeExecutor::new();
+
+        let result = command_me(&database, &mut fake, &HashMap::new()).await;
+
+        assert!(matches!(result, Err(QueryError::User(_))));
+        assert!(fake.statements.is_empty());
+    }
+}
@@ -0,0 +1,31 @@
+use super::*;
+
+pub async fn command_player_clan(
+    context: &Database,
+    connection: &mut dyn Executor,
+    arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let Some(player_uid) = arguments.get("uid") else {
+        return Err(QueryError::User(
+            "Missing key `uid` in provided query arguments".into(),
+        ));
+    };
+
+    // The outer option is if the account exists, the inner is if they are in a clan
+    let clan_id: Option<Option<u64>> = connection
+        .exec_first(
+            &context.sql.player_clan_lookup,
+            params! { "uid" => player_uid },
+        )
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    let Some(Some(clan_id)) = clan_id else {
+        return Ok(vec![]);
+    };
+
+    match queries::find_clan(context, connection, clan_id).await? {
+        Some(clan) => Ok(vec![serde_json::to_string(&clan).unwrap()]),
+        None => Ok(vec![]),
+    }
+}
@@ -23,7 +23,7 @@ struct Account {
 
 pub async fn command_player_info(
     context: &Database,
-    connection: &mut Conn,
+    connection: &mut dyn Executor,
     arguments: &HashMap<String, String>,
 ) -> QueryResult {
     let player_uid = arguments.get("uid").ok_or(QueryError::User(
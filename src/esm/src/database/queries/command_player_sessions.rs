@@ -0,0 +1,193 @@
+use super::*;
+
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 50;
+
+#[derive(Debug, Serialize)]
+struct PlayerSessions {
+    uid: String,
+    is_online: bool,
+    last_seen_at: NaiveDateTime,
+    session_count: u64,
+    playtime_seconds: u64,
+    recent_sessions: Vec<Session>,
+}
+
+#[derive(Debug, Serialize)]
+struct Session {
+    name: String,
+    connected_at: NaiveDateTime,
+    disconnected_at: Option<NaiveDateTime>,
+    duration_seconds: i64,
+}
+
+/// Playtime totals and the most recent sessions for a player
+/// Arguments:
+///     uid: The player's Steam UID
+///     limit: The number of recent sessions, 1 to 50. Defaults to 10
+pub async fn command_player_sessions(
+    context: &Database,
+    connection: &mut dyn Executor,
+    arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let Some(uid) = arguments.get("uid") else {
+        return Err(QueryError::User(
+            "Missing key `uid` in provided query arguments".into(),
+        ));
+    };
+
+    let limit = match arguments.get("limit") {
+        Some(limit) => match limit.parse::<usize>() {
+            Ok(limit) if (1..=MAX_LIMIT).contains(&limit) => limit,
+            _ => {
+                return Err(QueryError::User(format!(
+                    "`limit` must be a number between 1 and {MAX_LIMIT}"
+                )))
+            }
+        },
+        None => DEFAULT_LIMIT,
+    };
+
+    let totals: Option<Row> = connection
+        .exec_first(&context.sql.command_player_sessions, params! { uid })
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    let Some(totals) = totals else {
+        return Ok(vec![]);
+    };
+
+    let session_count: u64 = select_column(&totals, "session_count")
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    // The player has never connected while session tracking was enabled
+    if session_count == 0 {
+        return Ok(vec![]);
+    }
+
+    let recent_sessions = connection
+        .exec_map(
+            &context.sql.player_sessions_lookup,
+            params! {
+                "uid" => uid,
+                "limit" => limit
+            },
+            map_session,
+        )
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?
+        .into_iter()
+        .collect::<Result<Vec<Session>, String>>()
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    let sessions = map_results(uid, session_count, totals, recent_sessions)
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    Ok(vec![serde_json::to_string(&sessions).unwrap()])
+}
+
+fn map_results(
+    uid: &str,
+    session_count: u64,
+    row: Row,
+    recent_sessions: Vec<Session>,
+) -> Result<PlayerSessions, String> {
+    let open_session_count: u64 = select_column(&row, "open_session_count")?;
+
+    Ok(PlayerSessions {
+        uid: uid.to_owned(),
+        is_online: open_session_count > 0,
+        last_seen_at: select_column(&row, "last_seen_at")?,
+        session_count,
+        playtime_seconds: select_column(&row, "playtime_seconds")?,
+        recent_sessions,
+    })
+}
+
+fn map_session(row: Row) -> Result<Session, String> {
+    Ok(Session {
+        name: select_column(&row, "name")?,
+        connected_at: select_column(&row, "connected_at")?,
+        disconnected_at: select_column(&row, "disconnected_at")?,
+        duration_seconds: select_column(&row, "duration_seconds")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+    use mysql_async::Value;
+
+    fn arguments() -> HashMap<String, String> {
+        HashMap::from([("uid".to_string(), "76561198037177305".to_string())])
+    }
+
+    #[tokio::test]
+    async fn it_returns_totals_and_recent_sessions() {
+        let database = Fixture::database();
+
+        let mut fake = FakeExecutor::new()
+            .respond_with(
+                &database.sql.command_player_sessions,
+                vec![Fixture::result(&[
+                    ("session_count", Value::UInt(2)),
+                    ("playtime_seconds", Value::UInt(5400)),
+                    ("last_seen_at", Value::Date(2024, 12, 20, 18, 0, 0, 0)),
+                    ("open_session_count", Value::UInt(1)),
+                ])],
+            )
+            .respond_with(
+                &database.sql.player_sessions_lookup,
+                vec![
+                    Fixture::result(&[
+                        ("name", Value::from("Bryan")),
+                        ("connected_at", Value::Date(2024, 12, 20, 17, 30, 0, 0)),
+                        ("disconnected_at", Value::NULL),
+                        ("duration_seconds", Value::Int(1800)),
+                    ]),
+                    Fixture::result(&[
+                        ("name", Value::from("Bryan")),
+                        ("connected_at", Value::Date(2024, 12, 19, 12, 0, 0, 0)),
+                        ("disconnected_at", Value::Date(2024, 12, 19, 13, 0, 0, 0)),
+                        ("duration_seconds", Value::Int(3600)),
+                    ]),
+                ],
+            );
+
+        let results = command_player_sessions(&database, &mut fake, &arguments())
+            .await
+            .unwrap();
+
+        let sessions: JSONValue = serde_json::from_str(&results[0]).unwrap();
+
+        assert_eq!(sessions["is_online"], json!(true));
+        assert_eq!(sessions["playtime_seconds"], json!(5400));
+        assert_eq!(sessions["recent_sessions"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            sessions["recent_sessions"][0]["disconnected_at"],
+            JSONValue::Null
+        );
+    }
+
+    #[tokio::test]
+    async fn it_returns_nothing_for_players_without_sessions() {
+        let database = Fixture::database();
+
+        let mut fake = FakeExecutor::new().respond_with(
+            &database.sql.command_player_sessions,
+            vec![Fixture::result(&[
+                ("session_count", Value::UInt(0)),
+                ("playtime_seconds", Value::UInt(0)),
+                ("last_seen_at", Value::NULL),
+                ("open_session_count", Value::UInt(0)),
+            ])],
+        );
+
+        let results = command_player_sessions(&database, &mut fake, &arguments())
+            .await
+            .unwrap();
+
+        assert!(results.is_empty());
+    }
+}
@@ -36,7 +36,7 @@ pub struct Territory {
 
 pub async fn command_player_territories(
     context: &Database,
-    connection: &mut Conn,
+    connection: &mut dyn Executor,
     arguments: &HashMap<String, String>,
 ) -> QueryResult {
     let player_uid = match arguments.get("uid") {
@@ -125,7 +125,7 @@ pub fn map_results(mut row: Row) -> Result<Territory, Error> {
 
 pub async fn update_id_and_names(
     context: &Database,
-    connection: &mut Conn,
+    connection: &mut dyn Executor,
     mut territories: Vec<Territory>,
 ) -> Result<Vec<Territory>, QueryError> {
     let name_lookup = create_name_lookup(context, connection, &territories).await?;
@@ -152,7 +152,7 @@ pub async fn update_id_and_names(
 
 pub async fn create_name_lookup(
     context: &Database,
-    connection: &mut Conn,
+    connection: &mut dyn Executor,
     territories: &Vec<Territory>,
 ) -> Result<HashMap<String, String>, QueryError> {
     let uids = territories
@@ -177,3 +177,69 @@ pub async fn create_name_lookup(
         Err(e) => return Err(QueryError::System(format!("Query failed - {}", e))),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+    use mysql_async::Value;
+
+    #[tokio::test]
+    async fn it_resolves_builder_names() {
+        let database = Fixture::database();
+
+        let territory = Fixture::result(&[
+            ("id", Value::Int(1)),
+            ("owner_uid", Value::from("76561198037177305")),
+            ("owner_name", Value::from("Bryan")),
+            ("territory_name", Value::from("Home")),
+            ("radius", Value::Double(15.0)),
+            ("level", Value::Int(1)),
+            (
+                "flag_texture",
+                Value::from(
+                    "exile_assets\\texture\\flag\\flag_misc_knuckles_co.paa",
+                ),
+            ),
+            ("flag_stolen", Value::Int(0)),
+            ("last_paid_at", Value::Date(2024, 12, 20, 0, 0, 0, 0)),
+            (
+                "build_rights",
+                Value::from(r#"["76561198037177305","76561198025434405"]"#),
+            ),
+            ("moderators", Value::from(r#"["76561198037177305"]"#)),
+            ("object_count", Value::Int(10)),
+            ("esm_custom_id", Value::NULL),
+        ]);
+
+        let name_lookup =
+            replace_list(&database.sql.account_name_lookup, ":uids", 2);
+
+        let mut fake = FakeExecutor::new()
+            .respond_with(&database.sql.command_player_territories, vec![territory])
+            .respond_with(
+                &name_lookup,
+                vec![Fixture::result(&[
+                    ("uid", Value::from("76561198037177305")),
+                    ("name", Value::from("Bryan")),
+                ])],
+            );
+
+        let arguments =
+            HashMap::from([("uid".to_string(), "76561198037177305".to_string())]);
+
+        let results = command_player_territories(&database, &mut fake, &arguments)
+            .await
+            .unwrap();
+
+        let territory: JSONValue = serde_json::from_str(&results[0]).unwrap();
+
+        assert_eq!(territory["id"], json!(database.encode_territory_id("1")));
+        assert_eq!(territory["flag_stolen"], json!(false));
+        assert_eq!(territory["moderators"][0]["name"], json!("Bryan"));
+        assert_eq!(
+            territory["build_rights"][1]["name"],
+            json!("Name not found")
+        );
+    }
+}
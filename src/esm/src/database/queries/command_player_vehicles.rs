@@ -0,0 +1,142 @@
+use super::*;
+
+#[derive(Debug, Serialize)]
+pub struct Vehicle {
+    id: u64,
+    class: String,
+    nickname: String,
+    owner_uid: Option<String>,
+    position: Position,
+    damage: f64,
+    fuel: f64,
+    is_locked: bool,
+    territory_id: Option<String>,
+    territory_name: Option<String>,
+    last_updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize)]
+struct Position {
+    x: f64,
+    y: f64,
+    z: f64,
+}
+
+pub async fn command_player_vehicles(
+    context: &Database,
+    connection: &mut dyn Executor,
+    arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let Some(player_uid) = arguments.get("uid") else {
+        return Err(QueryError::User(
+            "Missing key `uid` in provided query arguments".into(),
+        ));
+    };
+
+    let result = connection
+        .exec_map(
+            &context.sql.command_player_vehicles,
+            params! { "uid" => player_uid },
+            |row: Row| map_vehicle_results(context, row),
+        )
+        .await;
+
+    match result {
+        Ok(vehicles) => vehicles_to_results(vehicles),
+        Err(e) => Err(QueryError::System(format!("Query failed - {}", e))),
+    }
+}
+
+/// Used by command_player_vehicles and command_territory_vehicles
+pub fn map_vehicle_results(context: &Database, row: Row) -> Result<Vehicle, String> {
+    let is_locked: isize = select_column(&row, "is_locked")?;
+    let territory_id: Option<String> = select_column(&row, "territory_id")?;
+
+    Ok(Vehicle {
+        id: select_column(&row, "id")?,
+        class: select_column(&row, "class")?,
+        nickname: select_column(&row, "nickname")?,
+        owner_uid: select_column(&row, "account_uid")?,
+        position: Position {
+            x: select_column(&row, "position_x")?,
+            y: select_column(&row, "position_y")?,
+            z: select_column(&row, "position_z")?,
+        },
+        damage: select_column(&row, "damage")?,
+        fuel: select_column(&row, "fuel")?,
+        is_locked: is_locked == 1,
+        territory_id: territory_id.map(|id| context.encode_territory_id(&id)),
+        territory_name: select_column(&row, "territory_name")?,
+        last_updated_at: select_column(&row, "last_updated_at")?,
+    })
+}
+
+pub fn vehicles_to_results(vehicles: Vec<Result<Vehicle, String>>) -> QueryResult {
+    let errors = vehicles
+        .iter()
+        .filter_map(|result| result.as_ref().err())
+        .map(|err| err.to_string())
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    if !errors.is_empty() {
+        return Err(QueryError::System(format!("Query failed - {}", errors)));
+    }
+
+    let results = vehicles
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter_map(|vehicle| serde_json::to_string(&vehicle).ok())
+        .collect();
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+    use mysql_async::Value;
+
+    #[tokio::test]
+    async fn it_returns_the_players_vehicles() {
+        let database = Fixture::database();
+
+        let vehicle = Fixture::result(&[
+            ("id", Value::Int(12)),
+            ("class", Value::from("Exile_Car_Hatchback_Rusty1")),
+            ("nickname", Value::from("")),
+            ("account_uid", Value::from("76561198037177305")),
+            ("position_x", Value::Double(1000.0)),
+            ("position_y", Value::Double(2000.0)),
+            ("position_z", Value::Double(0.0)),
+            ("damage", Value::Double(0.1)),
+            ("fuel", Value::Double(0.5)),
+            ("is_locked", Value::Int(1)),
+            ("territory_id", Value::from("1")),
+            ("territory_name", Value::from("Home")),
+            ("last_updated_at", Value::Date(2024, 12, 20, 0, 0, 0, 0)),
+        ]);
+
+        let mut fake = FakeExecutor::new()
+            .respond_with(&database.sql.command_player_vehicles, vec![vehicle]);
+
+        let arguments =
+            HashMap::from([("uid".to_string(), "76561198037177305".to_string())]);
+
+        let results = command_player_vehicles(&database, &mut fake, &arguments)
+            .await
+            .unwrap();
+
+        let vehicle: JSONValue = serde_json::from_str(&results[0]).unwrap();
+
+        assert_eq!(vehicle["id"], json!(12));
+        assert_eq!(vehicle["is_locked"], json!(true));
+        assert_eq!(vehicle["position"]["y"], json!(2000.0));
+        assert_eq!(
+            vehicle["territory_id"],
+            json!(database.encode_territory_id("1"))
+        );
+        assert_eq!(vehicle["territory_name"], json!("Home"));
+    }
+}
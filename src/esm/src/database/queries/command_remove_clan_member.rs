@@ -0,0 +1,128 @@
+use super::*;
+
+pub async fn command_remove_clan_member(
+    context: &Database,
+    connection: &mut dyn Executor,
+    arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let Some(clan_id) = arguments.get("clan_id") else {
+        return Err(QueryError::User(
+            "Missing key `clan_id` in provided query arguments".into(),
+        ));
+    };
+
+    let Some(member_uid) = arguments.get("uid") else {
+        return Err(QueryError::User(
+            "Missing key `uid` in provided query arguments".into(),
+        ));
+    };
+
+    let Ok(clan_id) = clan_id.parse::<u64>() else {
+        return Err(QueryError::User(format!(
+            "`clan_id` must be a number, got \"{clan_id}\""
+        )));
+    };
+
+    let Some(clan) = queries::find_clan(context, connection, clan_id).await? else {
+        return Err(QueryError::User(format!("Clan {clan_id} does not exist")));
+    };
+
+    if !clan.members.iter().any(|member| member.uid == *member_uid) {
+        return Err(QueryError::User(format!(
+            "{member_uid} is not a member of {}",
+            clan.name
+        )));
+    }
+
+    // Exile expects every clan to have a leader
+    if clan.leader_uid == *member_uid {
+        return Err(QueryError::User(format!(
+            "{member_uid} is the leader of {}. Set a new leader before removing them",
+            clan.name
+        )));
+    }
+
+    let result = connection
+        .exec_drop(
+            &context.sql.command_remove_clan_member,
+            params! {
+                "clan_id" => clan_id,
+                "uid" => member_uid
+            },
+        )
+        .await;
+
+    match result {
+        Ok(_) => Ok(vec![]),
+        Err(e) => Err(QueryError::System(format!("Query failed - {}", e))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+    use mysql_async::Value;
+
+    fn fake(database: &Database) -> FakeExecutor {
+        FakeExecutor::new()
+            .respond_with(
+                &database.sql.command_clan_info,
+                vec![Fixture::result(&[
+                    ("id", Value::Int(3)),
+                    ("name", Value::from("Knuckles")),
+                    ("leader_uid", Value::from("76561198037177305")),
+                    ("leader_name", Value::from("Bryan")),
+                    ("created_at", Value::Date(2024, 12, 20, 0, 0, 0, 0)),
+                    ("map_marker_count", Value::Int(0)),
+                ])],
+            )
+            .respond_with(
+                &database.sql.clan_members_lookup,
+                vec![Fixture::result(&[
+                    ("uid", Value::from("76561198037177305")),
+                    ("name", Value::from("Bryan")),
+                ])],
+            )
+            .respond_with(&database.sql.clan_territories_lookup, vec![])
+    }
+
+    fn arguments(uid: &str) -> HashMap<String, String> {
+        HashMap::from([
+            ("clan_id".to_string(), "3".to_string()),
+            ("uid".to_string(), uid.to_string()),
+        ])
+    }
+
+    #[tokio::test]
+    async fn it_does_not_remove_the_leader() {
+        let database = Fixture::database();
+        let mut fake = fake(&database);
+
+        let result = command_remove_clan_member(
+            &database,
+            &mut fake,
+            &arguments("76561198037177305"),
+        )
+        .await;
+
+        assert!(matches!(result, Err(QueryError::User(e)) if e.contains("leader")));
+    }
+
+    #[tokio::test]
+    async fn it_only_removes_members() {
+        let database = Fixture::database();
+        let mut fake = fake(&database);
+
+        let result = command_remove_clan_member(
+            &database,
+            &mut fake,
+            &arguments("76561198025434405"),
+        )
+        .await;
+
+        assert!(
+            matches!(result, Err(QueryError::User(e)) if e.contains("not a member"))
+        );
+    }
+}
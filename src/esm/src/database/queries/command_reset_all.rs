@@ -2,7 +2,7 @@ use super::*;
 
 pub async fn command_reset_all(
     context: &Database,
-    connection: &mut Conn,
+    connection: &mut dyn Executor,
     _arguments: &HashMap<String, String>,
 ) -> QueryResult {
     let result = connection.query_drop(&context.sql.command_reset_all).await;
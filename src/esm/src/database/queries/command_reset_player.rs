@@ -2,7 +2,7 @@ use super::*;
 
 pub async fn command_reset_player(
     context: &Database,
-    connection: &mut Conn,
+    connection: &mut dyn Executor,
     arguments: &HashMap<String, String>,
 ) -> QueryResult {
     let target_uid = arguments.get("uid").ok_or(QueryError::User(
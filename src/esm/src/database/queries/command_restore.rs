@@ -1,8 +1,15 @@
 use super::*;
 
+#[derive(Debug, Serialize)]
+struct DeletionRecord {
+    deleted_by_uid: String,
+    reason: Option<String>,
+    deleted_at: NaiveDateTime,
+}
+
 pub async fn command_restore(
     context: &Database,
-    connection: &mut Conn,
+    connection: &mut dyn Executor,
     arguments: &HashMap<String, String>,
 ) -> QueryResult {
     let Some(territory_id) = arguments.get("territory_id") else {
@@ -38,11 +45,46 @@ pub async fn command_restore(
     )
     .await?;
 
-    Ok(vec![])
+    // Territories deleted through ESM have a record of who deleted them. Mark it as restored
+    // and return it so the restore can be attributed
+    let record: Option<(u64, String, Option<String>, NaiveDateTime)> = connection
+        .exec_first(
+            &context.sql.territory_deletion_lookup,
+            params! { territory_id },
+        )
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    let Some((record_id, deleted_by_uid, reason, deleted_at)) = record else {
+        return Ok(vec![]);
+    };
+
+    connection
+        .exec_drop(
+            &context.sql.command_restore_territory_record,
+            params! {
+                "id" => record_id,
+                // Optional. Older bots don't send who restored the territory
+                "restored_by_uid" => arguments.get("steam_uid")
+            },
+        )
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    let record = DeletionRecord {
+        deleted_by_uid,
+        reason,
+        deleted_at,
+    };
+
+    let record = serde_json::to_string(&record)
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    Ok(vec![record])
 }
 
 async fn execute_statement(
-    connection: &mut Conn,
+    connection: &mut dyn Executor,
     statement: &str,
     territory_id: u64,
 ) -> Result<(), QueryError> {
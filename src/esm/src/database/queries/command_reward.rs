@@ -2,7 +2,7 @@ use super::*;
 
 pub async fn command_reward_territories(
     _context: &Database,
-    connection: &mut Conn,
+    connection: &mut dyn Executor,
     arguments: &HashMap<String, String>,
 ) -> QueryResult {
     let player_uid = match arguments.get("uid") {
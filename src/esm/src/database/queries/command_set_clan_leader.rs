@@ -0,0 +1,58 @@
+use super::*;
+
+pub async fn command_set_clan_leader(
+    context: &Database,
+    connection: &mut dyn Executor,
+    arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let Some(clan_id) = arguments.get("clan_id") else {
+        return Err(QueryError::User(
+            "Missing key `clan_id` in provided query arguments".into(),
+        ));
+    };
+
+    let Some(leader_uid) = arguments.get("uid") else {
+        return Err(QueryError::User(
+            "Missing key `uid` in provided query arguments".into(),
+        ));
+    };
+
+    let Ok(clan_id) = clan_id.parse::<u64>() else {
+        return Err(QueryError::User(format!(
+            "`clan_id` must be a number, got \"{clan_id}\""
+        )));
+    };
+
+    let Some(clan) = queries::find_clan(context, connection, clan_id).await? else {
+        return Err(QueryError::User(format!("Clan {clan_id} does not exist")));
+    };
+
+    if clan.leader_uid == *leader_uid {
+        return Err(QueryError::User(format!(
+            "{leader_uid} is already the leader of {}",
+            clan.name
+        )));
+    }
+
+    if !clan.members.iter().any(|member| member.uid == *leader_uid) {
+        return Err(QueryError::User(format!(
+            "{leader_uid} is not a member of {}",
+            clan.name
+        )));
+    }
+
+    let result = connection
+        .exec_drop(
+            &context.sql.command_set_clan_leader,
+            params! {
+                "clan_id" => clan_id,
+                "leader_uid" => leader_uid
+            },
+        )
+        .await;
+
+    match result {
+        Ok(_) => Ok(vec![]),
+        Err(e) => Err(QueryError::System(format!("Query failed - {}", e))),
+    }
+}
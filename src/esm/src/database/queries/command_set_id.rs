@@ -1,8 +1,18 @@
 use super::*;
 
+use crate::config::Config;
+
+/// Gives a territory a custom ID that can be used in place of its encoded ID.
+/// Territory admins skip the cooldown, and their changes don't start the owner's cooldown
+/// Arguments:
+///     steam_uid: The player making the change. Must own the territory unless they are a
+///         territory admin
+///     territory_id: The territory's current encoded or custom ID
+///     new_territory_id: The custom ID. Must follow the custom_territory_id rules in
+///         @esm/config.yml
 pub async fn command_set_id(
     context: &Database,
-    connection: &mut Conn,
+    connection: &mut dyn Executor,
     arguments: &HashMap<String, String>,
 ) -> QueryResult {
     let Some(steam_uid) = arguments.get("steam_uid") else {
@@ -23,13 +33,25 @@ pub async fn command_set_id(
         ));
     };
 
+    check_custom_id(&crate::CONFIG, new_territory_id)?;
+
+    // Encoded IDs are checked before custom IDs. This custom ID would never be reached.
+    // Only the salts that exist now are checked. A salt added when esm.key changes could
+    // still encode to this ID, and that territory would win
+    if context.hasher.decode(&new_territory_id.to_lowercase()).is_some() {
+        return Err(QueryError::User(format!(
+            "`{new_territory_id}` can't be used because it looks like an encoded territory ID"
+        )));
+    }
+
     // This handles both hashed IDs or custom
     let territory_id =
         queries::decode_territory_id(context, connection, territory_id).await?;
 
     // Territory admins can bypass this check.
     // Otherwise, check to see if the steam_uid is the owner's
-    if !arma::is_territory_admin(&steam_uid) {
+    let is_admin = arma::is_territory_admin(steam_uid);
+    if !is_admin {
         let is_owner = queries::check_if_territory_owner(
             context,
             connection,
@@ -45,6 +67,26 @@ pub async fn command_set_id(
         }
     }
 
+    check_availability(context, connection, territory_id, new_territory_id).await?;
+
+    if !is_admin {
+        check_cooldown(context, connection, territory_id).await?;
+    }
+
+    // The history records the previous custom ID, so it must be written first
+    connection
+        .exec_drop(
+            &context.sql.add_territory_custom_id_history,
+            params! {
+                "territory_id" => territory_id,
+                "custom_id" => new_territory_id,
+                "changed_by_uid" => steam_uid,
+                "changed_by_admin" => is_admin
+            },
+        )
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
     let result = connection
         .exec_drop(
             &context.sql.command_set_id,
@@ -60,3 +102,194 @@ pub async fn command_set_id(
         Err(e) => Err(QueryError::System(format!("Query failed - {}", e))),
     }
 }
+
+/// Checks a custom ID against the custom_territory_id rules. Custom IDs are matched
+/// case-insensitively, so the rules are too
+pub fn check_custom_id(config: &Config, custom_id: &str) -> Result<(), QueryError> {
+    let lowercase = custom_id.to_lowercase();
+    let length = custom_id.chars().count();
+    let (min, max) = (
+        config.custom_territory_id_min_length,
+        config.custom_territory_id_max_length,
+    );
+
+    if length < min || length > max {
+        return Err(QueryError::User(format!(
+            "`new_territory_id` must be between {min} and {max} characters"
+        )));
+    }
+
+    let allowed = config.custom_territory_id_allowed_characters.to_lowercase();
+    if let Some(character) = lowercase.chars().find(|c| !allowed.contains(*c)) {
+        return Err(QueryError::User(format!(
+            "`new_territory_id` can't contain \"{character}\". Allowed characters: {allowed}"
+        )));
+    }
+
+    if config
+        .custom_territory_id_reserved_words
+        .iter()
+        .any(|word| word.to_lowercase() == lowercase)
+    {
+        return Err(QueryError::User(format!(
+            "`{custom_id}` is reserved and can't be used"
+        )));
+    }
+
+    if config
+        .custom_territory_id_blocklist
+        .iter()
+        .any(|word| !word.is_empty() && lowercase.contains(&word.to_lowercase()))
+    {
+        return Err(QueryError::User(format!(
+            "`{custom_id}` contains a blocked word"
+        )));
+    }
+
+    Ok(())
+}
+
+// Another territory may be using the custom ID, or have used it recently enough that it
+// still redirects to them
+async fn check_availability(
+    context: &Database,
+    connection: &mut dyn Executor,
+    territory_id: u64,
+    custom_id: &str,
+) -> Result<(), QueryError> {
+    let current: Option<u64> = connection
+        .exec_first(
+            &context.sql.decode_territory_id,
+            params! { "custom_id" => custom_id },
+        )
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    match current {
+        Some(id) if id == territory_id => {
+            return Err(QueryError::User(format!(
+                "`{custom_id}` is already this territory's ID"
+            )))
+        }
+        Some(_) => {
+            return Err(QueryError::User(format!(
+                "`{custom_id}` is already in use by another territory"
+            )))
+        }
+        None => {}
+    }
+
+    let redirect_days = crate::CONFIG.custom_territory_id_redirect_days;
+    let redirect: Option<u64> = connection
+        .exec_first(
+            &context.sql.territory_custom_id_redirect,
+            params! {
+                "custom_id" => custom_id,
+                "redirect_days" => redirect_days
+            },
+        )
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    match redirect {
+        Some(id) if id != territory_id => Err(QueryError::User(format!(
+            "`{custom_id}` was used by another territory within the last {redirect_days} days and can't be claimed yet"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+async fn check_cooldown(
+    context: &Database,
+    connection: &mut dyn Executor,
+    territory_id: u64,
+) -> Result<(), QueryError> {
+    let cooldown_seconds =
+        crate::CONFIG.custom_territory_id_cooldown_hours as i64 * 3600;
+
+    if cooldown_seconds == 0 {
+        return Ok(());
+    }
+
+    let seconds_since_change: Option<Option<i64>> = connection
+        .exec_first(
+            &context.sql.territory_custom_id_cooldown,
+            params! { "territory_id" => territory_id },
+        )
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    match seconds_since_change.flatten() {
+        Some(seconds) if seconds < cooldown_seconds => {
+            let remaining = cooldown_seconds - seconds;
+
+            Err(QueryError::User(format!(
+                "This territory's ID was changed recently. It can be changed again in {}h {}m",
+                remaining / 3600,
+                remaining % 3600 / 60
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+
+    #[test]
+    fn it_checks_the_custom_id_rules() {
+        let config = Config {
+            custom_territory_id_blocklist: vec!["heck".into()],
+            ..Config::default()
+        };
+
+        assert!(check_custom_id(&config, "home_base-1").is_ok());
+        assert!(check_custom_id(&config, "MyBase").is_ok());
+
+        for custom_id in ["ab", "a".repeat(21).as_str(), "my base", "café"] {
+            assert!(
+                matches!(
+                    check_custom_id(&config, custom_id),
+                    Err(QueryError::User(e)) if e.contains("`new_territory_id`")
+                ),
+                "{}",
+                custom_id
+            );
+        }
+
+        assert!(matches!(
+            check_custom_id(&config, "esm"),
+            Err(QueryError::User(e)) if e.contains("reserved")
+        ));
+
+        assert!(matches!(
+            check_custom_id(&config, "what_the_heck"),
+            Err(QueryError::User(e)) if e.contains("blocked")
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_rejects_encoded_territory_ids() {
+        let database = Fixture::database();
+        let mut fake = FakeExecutor::new();
+
+        let arguments = HashMap::from([
+            ("steam_uid".to_string(), "76561198037177305".to_string()),
+            ("territory_id".to_string(), "home".to_string()),
+            (
+                "new_territory_id".to_string(),
+                database.encode_territory_id("5"),
+            ),
+        ]);
+
+        let result = command_set_id(&database, &mut fake, &arguments).await;
+
+        assert!(matches!(
+            result,
+            Err(QueryError::User(e)) if e.contains("encoded territory ID")
+        ));
+        assert!(fake.statements.is_empty());
+    }
+}
@@ -0,0 +1,168 @@
+use super::*;
+
+/// Turns an XM8 notification type on or off for a player, and/or sets their quiet hours
+/// Arguments:
+///     uid: The player's Steam UID
+///     type: The notification type to change. Requires `enabled`
+///     enabled: "true" or "false"
+///     quiet_hours_start: "HH:MM" in UTC. Requires `quiet_hours_end`
+///     quiet_hours_end: "HH:MM" in UTC. Setting both to an empty string removes quiet hours
+pub async fn command_set_xm8_preferences(
+    context: &Database,
+    connection: &mut dyn Executor,
+    arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let Some(uid) = arguments.get("uid") else {
+        return Err(QueryError::User(
+            "Missing key `uid` in provided query arguments".into(),
+        ));
+    };
+
+    let preference = match (arguments.get("type"), arguments.get("enabled")) {
+        (Some(notification_type), Some(enabled)) => {
+            let enabled = match enabled.as_str() {
+                "true" => true,
+                "false" => false,
+                _ => {
+                    return Err(QueryError::User(format!(
+                        "`enabled` must be \"true\" or \"false\", got \"{enabled}\""
+                    )))
+                }
+            };
+
+            let notification_type = notification_type
+                .parse::<NotificationType>()
+                .map_err(QueryError::User)?;
+
+            Some((notification_type.as_str(), enabled))
+        }
+        (None, None) => None,
+        _ => {
+            return Err(QueryError::User(
+                "`type` and `enabled` must be provided together".into(),
+            ))
+        }
+    };
+
+    let quiet_hours = match (
+        arguments.get("quiet_hours_start"),
+        arguments.get("quiet_hours_end"),
+    ) {
+        (Some(starts_at), Some(ends_at))
+            if starts_at.is_empty() && ends_at.is_empty() =>
+        {
+            Some(None)
+        }
+        (Some(starts_at), Some(ends_at)) => {
+            Some(Some((parse_time(starts_at)?, parse_time(ends_at)?)))
+        }
+        (None, None) => None,
+        _ => return Err(QueryError::User(
+            "`quiet_hours_start` and `quiet_hours_end` must be provided together"
+                .into(),
+        )),
+    };
+
+    if preference.is_none() && quiet_hours.is_none() {
+        return Err(QueryError::User(
+            "Nothing to update. Provide `type` and `enabled`, or `quiet_hours_start` and `quiet_hours_end`".into(),
+        ));
+    }
+
+    if !queries::check_if_account_exists(context, connection, uid).await? {
+        return Err(QueryError::User(format!("{uid} has not joined the server")));
+    }
+
+    if let Some((notification_type, enabled)) = preference {
+        connection
+            .exec_drop(
+                &context.sql.set_xm8_preference,
+                params! {
+                    "uid" => uid,
+                    "type" => notification_type,
+                    "enabled" => enabled
+                },
+            )
+            .await
+            .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+    }
+
+    match quiet_hours {
+        Some(Some((starts_at, ends_at))) => connection
+            .exec_drop(
+                &context.sql.set_xm8_quiet_hours,
+                params! {
+                    "uid" => uid,
+                    "starts_at" => starts_at,
+                    "ends_at" => ends_at
+                },
+            )
+            .await
+            .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?,
+        Some(None) => connection
+            .exec_drop(&context.sql.clear_xm8_quiet_hours, params! { "uid" => uid })
+            .await
+            .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?,
+        None => {}
+    }
+
+    let preferences =
+        queries::find_xm8_preferences(context, connection, uid).await?;
+
+    let preferences = serde_json::to_string(&preferences)
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    Ok(vec![preferences])
+}
+
+fn parse_time(input: &str) -> Result<NaiveTime, QueryError> {
+    NaiveTime::parse_from_str(input, "%H:%M").map_err(|_| {
+        QueryError::User(format!(
+            "Quiet hours must be formatted as HH:MM, got \"{input}\""
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+
+    fn arguments(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        let mut arguments =
+            HashMap::from([("uid".to_string(), "76561198037177305".to_string())]);
+
+        arguments.extend(
+            pairs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string())),
+        );
+
+        arguments
+    }
+
+    #[tokio::test]
+    async fn it_validates_the_arguments() {
+        let database = Fixture::database();
+
+        for pairs in [
+            vec![],
+            vec![("type", "base-raid")],
+            vec![("type", "base-raid"), ("enabled", "no")],
+            vec![("type", "hacking-started"), ("enabled", "false")],
+            vec![("quiet_hours_start", "22:00")],
+            vec![("quiet_hours_start", "10pm"), ("quiet_hours_end", "07:00")],
+        ] {
+            let mut fake = FakeExecutor::new();
+            let result = command_set_xm8_preferences(
+                &database,
+                &mut fake,
+                &arguments(&pairs),
+            )
+            .await;
+
+            assert!(matches!(result, Err(QueryError::User(_))), "{:?}", pairs);
+            assert!(fake.statements.is_empty());
+        }
+    }
+}
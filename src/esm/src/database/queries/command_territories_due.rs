@@ -0,0 +1,160 @@
+use super::*;
+
+#[derive(Debug, Serialize)]
+pub struct DueTerritory {
+    pub id: String,
+    #[serde(skip)]
+    pub database_id: u64,
+    pub name: String,
+    pub owner_uid: String,
+    pub owner_name: String,
+    pub build_rights: Vec<String>,
+    pub last_paid_at: NaiveDateTime,
+    pub due_at: NaiveDateTime,
+    pub minutes_remaining: i64,
+}
+
+pub async fn command_territories_due(
+    context: &Database,
+    connection: &mut dyn Executor,
+    arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let within_hours = match arguments.get("within_hours") {
+        Some(hours) => match hours.parse::<u64>() {
+            Ok(hours) if hours > 0 => hours,
+            _ => {
+                return Err(QueryError::User(format!(
+                "`within_hours` must be a number greater than zero, got \"{hours}\""
+            )))
+            }
+        },
+        None => 24,
+    };
+
+    let territories =
+        find_territories_due(context, connection, within_hours).await?;
+
+    Ok(territories
+        .iter()
+        .filter_map(|territory| serde_json::to_string(territory).ok())
+        .collect())
+}
+
+/// Territories that are still active but will expire within the given number of hours,
+/// soonest first. Used by command_territories_due and queue_territory_reminders
+pub async fn find_territories_due(
+    context: &Database,
+    connection: &mut dyn Executor,
+    within_hours: u64,
+) -> Result<Vec<DueTerritory>, QueryError> {
+    let territory_lifetime = crate::bot::territory_lifetime();
+
+    if territory_lifetime == 0 {
+        return Err(QueryError::System(
+            "Territory lifetime is not known yet. Has the server sent init?".into(),
+        ));
+    }
+
+    let territories = connection
+        .exec_map(
+            &context.sql.command_territories_due,
+            params! {
+                "territory_lifetime" => territory_lifetime,
+                "within_hours" => within_hours
+            },
+            |row: Row| map_results(context, row),
+        )
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    territories
+        .into_iter()
+        .collect::<Result<Vec<_>, String>>()
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))
+}
+
+fn map_results(context: &Database, row: Row) -> Result<DueTerritory, String> {
+    let id: String = select_column(&row, "id")?;
+    let build_rights: String = select_column(&row, "build_rights")?;
+
+    Ok(DueTerritory {
+        id: context.encode_territory_id(&id),
+        database_id: id.parse().map_err(|e| format!("{e}"))?,
+        name: select_column(&row, "name")?,
+        owner_uid: select_column(&row, "owner_uid")?,
+        owner_name: select_column(&row, "owner_name")?,
+        build_rights: serde_json::from_str(&build_rights).unwrap_or_default(),
+        last_paid_at: select_column(&row, "last_paid_at")?,
+        due_at: select_column(&row, "due_at")?,
+        minutes_remaining: select_column(&row, "minutes_remaining")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+    use mysql_async::Value;
+
+    #[tokio::test]
+    async fn it_rejects_invalid_within_hours() {
+        let database = Fixture::database();
+        let mut fake = FakeExecutor::new();
+
+        for hours in ["0", "-1", "soon"] {
+            let arguments =
+                HashMap::from([("within_hours".to_string(), hours.to_string())]);
+
+            let result =
+                command_territories_due(&database, &mut fake, &arguments).await;
+
+            assert!(
+                matches!(result, Err(QueryError::User(ref e)) if e.contains("within_hours")),
+                "{}",
+                hours
+            );
+        }
+
+        assert!(fake.statements.is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_waits_for_the_territory_lifetime() {
+        let database = Fixture::database();
+        let mut fake = FakeExecutor::new();
+
+        // The server hasn't sent init in tests, so the lifetime is unknown
+        let result =
+            command_territories_due(&database, &mut fake, &HashMap::new()).await;
+
+        assert!(matches!(result, Err(QueryError::System(e)) if e.contains("init")));
+        assert!(fake.statements.is_empty());
+    }
+
+    #[test]
+    fn it_maps_due_territories() {
+        let database = Fixture::database();
+        let due_at = NaiveDateTime::default();
+
+        let row = Fixture::result(&[
+            ("id", Value::from("5")),
+            ("name", Value::from("Home")),
+            ("owner_uid", Value::from("76561198037177305")),
+            ("owner_name", Value::from("Bryan")),
+            ("build_rights", Value::from("[\"76561198037177305\"]")),
+            ("last_paid_at", Value::from(due_at)),
+            ("due_at", Value::from(due_at)),
+            ("minutes_remaining", Value::Int(90)),
+        ]);
+
+        let territory = map_results(&database, row).unwrap();
+
+        assert_eq!(territory.id, database.encode_territory_id("5"));
+        assert_eq!(territory.database_id, 5);
+        assert_eq!(territory.build_rights, vec!["76561198037177305"]);
+        assert_eq!(territory.minutes_remaining, 90);
+
+        let json = serde_json::to_value(&territory).unwrap();
+        assert!(json.get("database_id").is_none());
+    }
+}
@@ -2,7 +2,7 @@ use super::*;
 
 pub async fn command_territory_info(
     context: &Database,
-    connection: &mut Conn,
+    connection: &mut dyn Executor,
     arguments: &HashMap<String, String>,
 ) -> QueryResult {
     let territory_id =
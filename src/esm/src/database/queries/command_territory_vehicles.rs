@@ -0,0 +1,30 @@
+use super::*;
+
+pub async fn command_territory_vehicles(
+    context: &Database,
+    connection: &mut dyn Executor,
+    arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let Some(territory_id) = arguments.get("territory_id") else {
+        return Err(QueryError::User(
+            "Missing key `territory_id` in provided query arguments".into(),
+        ));
+    };
+
+    // This handles both hashed IDs or custom
+    let territory_id =
+        queries::decode_territory_id(context, connection, territory_id).await?;
+
+    let result = connection
+        .exec_map(
+            &context.sql.command_territory_vehicles,
+            params! { territory_id },
+            |row: Row| map_vehicle_results(context, row),
+        )
+        .await;
+
+    match result {
+        Ok(vehicles) => vehicles_to_results(vehicles),
+        Err(e) => Err(QueryError::System(format!("Query failed - {}", e))),
+    }
+}
@@ -0,0 +1,233 @@
+use super::*;
+
+#[derive(Debug, Serialize)]
+struct TransferResult {
+    territory_id: String,
+    territory_name: String,
+    previous_owner_uid: String,
+    new_owner_uid: String,
+}
+
+pub async fn command_transfer_territory(
+    context: &Database,
+    connection: &mut dyn Executor,
+    arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let Some(territory_id) = arguments.get("territory_id") else {
+        return Err(QueryError::User(
+            "Missing key `territory_id` in provided query arguments".into(),
+        ));
+    };
+
+    let Some(new_owner_uid) = arguments.get("new_owner_uid") else {
+        return Err(QueryError::User(
+            "Missing key `new_owner_uid` in provided query arguments".into(),
+        ));
+    };
+
+    // This handles both hashed IDs or custom IDs
+    let territory_id =
+        queries::decode_territory_id(context, connection, territory_id).await?;
+
+    if !queries::check_if_account_exists(context, connection, new_owner_uid).await? {
+        return Err(QueryError::User(format!(
+            "{new_owner_uid} has not joined the server"
+        )));
+    }
+
+    let territory: Option<(String, String, String, String)> = connection
+        .exec_first(
+            &context.sql.territory_ownership_lookup,
+            params! { territory_id },
+        )
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    let Some((previous_owner_uid, territory_name, build_rights, moderators)) =
+        territory
+    else {
+        return Err(QueryError::Code("territory_id_does_not_exist".into()));
+    };
+
+    if previous_owner_uid == *new_owner_uid {
+        return Err(QueryError::User(format!(
+            "{new_owner_uid} already owns {territory_name}"
+        )));
+    }
+
+    let build_rights =
+        transfer_rights(&build_rights, &previous_owner_uid, new_owner_uid)?;
+    let moderators =
+        transfer_rights(&moderators, &previous_owner_uid, new_owner_uid)?;
+
+    let result = connection
+        .exec_drop(
+            &context.sql.command_transfer_territory,
+            params! {
+                "territory_id" => territory_id,
+                "owner_uid" => new_owner_uid,
+                "build_rights" => build_rights,
+                "moderators" => moderators
+            },
+        )
+        .await;
+
+    if let Err(e) = result {
+        return Err(QueryError::System(format!("Query failed - {}", e)));
+    }
+
+    // Runs in the same transaction as the transfer, so a notification that can't be queued
+    // undoes the transfer. Each player's preferences and rate limits still decide whether
+    // they receive it
+    queries::add_xm8_notifications(
+        context,
+        connection,
+        NotificationType::TerritoryTransferred,
+        json!([previous_owner_uid, new_owner_uid]).to_string(),
+        HashMap::from([
+            ("territory_id".into(), territory_id.to_string()),
+            ("territory_name".into(), territory_name.to_owned()),
+            ("previous_owner_uid".into(), previous_owner_uid.to_owned()),
+            ("new_owner_uid".into(), new_owner_uid.to_owned()),
+        ]),
+    )
+    .await?;
+
+    let result = TransferResult {
+        territory_id: context.encode_territory_id(&territory_id.to_string()),
+        territory_name,
+        previous_owner_uid,
+        new_owner_uid: new_owner_uid.to_owned(),
+    };
+
+    Ok(vec![serde_json::to_string(&result).unwrap()])
+}
+
+/// Exile stores the owner in build rights and moderators. The previous owner is replaced by the
+/// new owner, who is moved to the front, and everyone else keeps their rights
+fn transfer_rights(
+    rights: &str,
+    previous_owner_uid: &str,
+    new_owner_uid: &str,
+) -> Result<String, QueryError> {
+    let rights: Vec<String> = serde_json::from_str(rights).map_err(|e| {
+        QueryError::System(format!(
+            "Failed to parse territory rights {rights:?} - {e}"
+        ))
+    })?;
+
+    let mut transferred = vec![new_owner_uid.to_owned()];
+
+    transferred.extend(
+        rights
+            .into_iter()
+            .filter(|uid| uid != previous_owner_uid && uid != new_owner_uid),
+    );
+
+    Ok(serde_json::to_string(&transferred).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+    use mysql_async::Value;
+
+    fn arguments(
+        database: &Database,
+        new_owner_uid: &str,
+    ) -> HashMap<String, String> {
+        HashMap::from([
+            (
+                "territory_id".to_string(),
+                database.encode_territory_id("5"),
+            ),
+            ("new_owner_uid".to_string(), new_owner_uid.to_string()),
+        ])
+    }
+
+    fn fake(database: &Database) -> FakeExecutor {
+        FakeExecutor::new()
+            .respond_with(
+                &database.sql.check_if_territory_exists,
+                vec![Fixture::result(&[("exists", Value::from("true"))])],
+            )
+            .respond_with(
+                &database.sql.check_if_account_exists,
+                vec![Fixture::result(&[("exists", Value::from("true"))])],
+            )
+            .respond_with(
+                &database.sql.territory_ownership_lookup,
+                vec![Fixture::result(&[
+                    ("owner_uid", Value::from("76561198037177305")),
+                    ("name", Value::from("Home")),
+                    ("build_rights", Value::from(r#"["76561198037177305"]"#)),
+                    ("moderators", Value::from(r#"["76561198037177305"]"#)),
+                ])],
+            )
+    }
+
+    #[tokio::test]
+    async fn it_rejects_transfers_to_the_current_owner() {
+        let database = Fixture::database();
+        let mut fake = fake(&database);
+
+        let result = command_transfer_territory(
+            &database,
+            &mut fake,
+            &arguments(&database, "76561198037177305"),
+        )
+        .await;
+
+        assert!(
+            matches!(result, Err(QueryError::User(e)) if e.contains("already owns"))
+        );
+    }
+
+    #[tokio::test]
+    async fn it_transfers_after_checking_the_new_owner() {
+        let database = Fixture::database();
+        let mut fake = fake(&database);
+
+        // The fake is read-only, so the transfer itself fails and nothing is queued
+        let result = command_transfer_territory(
+            &database,
+            &mut fake,
+            &arguments(&database, "76561198018283626"),
+        )
+        .await;
+
+        assert!(
+            matches!(result, Err(QueryError::System(e)) if e.contains("read-only"))
+        );
+        assert_eq!(
+            fake.statements,
+            vec![
+                database.sql.check_if_territory_exists.to_owned(),
+                database.sql.check_if_account_exists.to_owned(),
+                database.sql.territory_ownership_lookup.to_owned(),
+                database.sql.command_transfer_territory.to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_replaces_the_previous_owner() {
+        let rights =
+            r#"["76561198037177305","76561198025434405","76561198018283626"]"#;
+
+        let result =
+            transfer_rights(rights, "76561198037177305", "76561198018283626")
+                .unwrap();
+
+        assert_eq!(result, r#"["76561198018283626","76561198025434405"]"#);
+    }
+
+    #[test]
+    fn it_handles_empty_rights() {
+        let result = transfer_rights("[]", "76561198037177305", "76561198018283626");
+
+        assert_eq!(result.unwrap(), r#"["76561198018283626"]"#);
+        assert!(transfer_rights("", "1", "2").is_err());
+    }
+}
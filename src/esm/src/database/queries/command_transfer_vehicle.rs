@@ -0,0 +1,155 @@
+use super::*;
+
+#[derive(Debug, Serialize)]
+struct TransferResult {
+    vehicle_id: u64,
+    previous_owner_uid: Option<String>,
+    new_owner_uid: String,
+}
+
+pub async fn command_transfer_vehicle(
+    context: &Database,
+    connection: &mut dyn Executor,
+    arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let Some(vehicle_id) = arguments.get("vehicle_id") else {
+        return Err(QueryError::User(
+            "Missing key `vehicle_id` in provided query arguments".into(),
+        ));
+    };
+
+    let Some(new_owner_uid) = arguments.get("new_owner_uid") else {
+        return Err(QueryError::User(
+            "Missing key `new_owner_uid` in provided query arguments".into(),
+        ));
+    };
+
+    let Ok(vehicle_id) = vehicle_id.parse::<u64>() else {
+        return Err(QueryError::User(format!(
+            "`vehicle_id` must be a number, got \"{vehicle_id}\""
+        )));
+    };
+
+    let previous_owner_uid: Option<Option<String>> = connection
+        .exec_first(
+            &context.sql.vehicle_owner_lookup,
+            params! { "vehicle_id" => vehicle_id },
+        )
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    // A vehicle may not have an owner, which is different from the vehicle not existing
+    let Some(previous_owner_uid) = previous_owner_uid else {
+        return Err(QueryError::User(format!(
+            "Vehicle {vehicle_id} does not exist"
+        )));
+    };
+
+    if previous_owner_uid.as_ref() == Some(new_owner_uid) {
+        return Err(QueryError::User(format!(
+            "Vehicle {vehicle_id} is already owned by {new_owner_uid}"
+        )));
+    }
+
+    if !queries::check_if_account_exists(context, connection, new_owner_uid).await? {
+        return Err(QueryError::User(format!(
+            "{new_owner_uid} has not joined the server"
+        )));
+    }
+
+    let result = connection
+        .exec_drop(
+            &context.sql.command_transfer_vehicle,
+            params! {
+                "vehicle_id" => vehicle_id,
+                "new_owner_uid" => new_owner_uid
+            },
+        )
+        .await;
+
+    match result {
+        Ok(_) => {
+            let result = TransferResult {
+                vehicle_id,
+                previous_owner_uid,
+                new_owner_uid: new_owner_uid.to_owned(),
+            };
+
+            Ok(vec![serde_json::to_string(&result).unwrap()])
+        }
+        Err(e) => Err(QueryError::System(format!("Query failed - {}", e))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+    use mysql_async::Value;
+
+    fn arguments(vehicle_id: &str) -> HashMap<String, String> {
+        HashMap::from([
+            ("vehicle_id".to_string(), vehicle_id.to_string()),
+            ("new_owner_uid".to_string(), "76561198037177305".to_string()),
+        ])
+    }
+
+    #[tokio::test]
+    async fn it_validates_the_vehicle() {
+        let database = Fixture::database();
+
+        let mut fake = FakeExecutor::new();
+        let result =
+            command_transfer_vehicle(&database, &mut fake, &arguments("twelve"))
+                .await;
+
+        assert!(matches!(result, Err(QueryError::User(_))));
+
+        let mut fake = FakeExecutor::new()
+            .respond_with(&database.sql.vehicle_owner_lookup, vec![]);
+
+        let result =
+            command_transfer_vehicle(&database, &mut fake, &arguments("12")).await;
+
+        assert!(
+            matches!(result, Err(QueryError::User(e)) if e.contains("does not exist"))
+        );
+
+        let mut fake = FakeExecutor::new().respond_with(
+            &database.sql.vehicle_owner_lookup,
+            vec![Fixture::result(&[(
+                "account_uid",
+                Value::from("76561198037177305"),
+            )])],
+        );
+
+        let result =
+            command_transfer_vehicle(&database, &mut fake, &arguments("12")).await;
+
+        assert!(
+            matches!(result, Err(QueryError::User(e)) if e.contains("already owned"))
+        );
+    }
+
+    #[tokio::test]
+    async fn it_requires_the_new_owner_to_exist() {
+        let database = Fixture::database();
+
+        let mut fake = FakeExecutor::new()
+            .respond_with(
+                &database.sql.vehicle_owner_lookup,
+                vec![Fixture::result(&[("account_uid", Value::NULL)])],
+            )
+            .respond_with(
+                &database.sql.check_if_account_exists,
+                vec![Fixture::result(&[("exists", Value::from("false"))])],
+            );
+
+        let result =
+            command_transfer_vehicle(&database, &mut fake, &arguments("12")).await;
+
+        assert!(
+            matches!(result, Err(QueryError::User(e)) if e.contains("has not joined"))
+        );
+    }
+}
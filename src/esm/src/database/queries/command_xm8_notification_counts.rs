@@ -0,0 +1,76 @@
+use super::*;
+
+// Limit tampering
+fn query() -> &'static str {
+    r#"
+    SELECT
+        state,
+        COUNT(*) as count
+    FROM
+        xm8_notification
+    GROUP BY
+        state;
+    "#
+}
+
+/// The number of notifications in each state
+pub async fn command_xm8_notification_counts(
+    _context: &Database,
+    connection: &mut dyn Executor,
+    _arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let counts: Vec<(String, u64)> = connection
+        .query_map(query(), |row| row)
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    // Every state is included, even if there aren't any notifications in it
+    let mut results = serde_json::Map::new();
+    for state in ["new", "pending", "sent", "failed"] {
+        let count = counts
+            .iter()
+            .find(|(s, _)| s == state)
+            .map(|(_, count)| *count)
+            .unwrap_or_default();
+
+        results.insert(state.into(), json!(count));
+    }
+
+    Ok(vec![JSONValue::Object(results).to_string()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+    use mysql_async::Value;
+
+    #[tokio::test]
+    async fn it_counts_every_state() {
+        let database = Fixture::database();
+
+        let mut fake = FakeExecutor::new().respond_with(
+            query(),
+            vec![
+                Fixture::result(&[
+                    ("state", Value::from("sent")),
+                    ("count", Value::Int(40)),
+                ]),
+                Fixture::result(&[
+                    ("state", Value::from("failed")),
+                    ("count", Value::Int(2)),
+                ]),
+            ],
+        );
+
+        let results =
+            command_xm8_notification_counts(&database, &mut fake, &HashMap::new())
+                .await
+                .unwrap();
+
+        assert_eq!(
+            results[0],
+            json!({"new": 0, "pending": 0, "sent": 40, "failed": 2}).to_string()
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use super::*;
+
+#[derive(Debug, Serialize)]
+pub struct Preferences {
+    pub uid: String,
+    pub disabled_types: Vec<String>,
+    pub quiet_hours: Option<QuietHours>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QuietHours {
+    pub starts_at: String,
+    pub ends_at: String,
+}
+
+/// The XM8 notification types the player turned off and their quiet hours (UTC)
+/// Arguments:
+///     uid: The player's Steam UID
+pub async fn command_xm8_preferences(
+    context: &Database,
+    connection: &mut dyn Executor,
+    arguments: &HashMap<String, String>,
+) -> QueryResult {
+    let Some(uid) = arguments.get("uid") else {
+        return Err(QueryError::User(
+            "Missing key `uid` in provided query arguments".into(),
+        ));
+    };
+
+    let preferences = find_xm8_preferences(context, connection, uid).await?;
+
+    let preferences = serde_json::to_string(&preferences)
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    Ok(vec![preferences])
+}
+
+/// Used by command_xm8_preferences and command_set_xm8_preferences
+pub async fn find_xm8_preferences(
+    context: &Database,
+    connection: &mut dyn Executor,
+    uid: &str,
+) -> Result<Preferences, QueryError> {
+    let disabled_types = connection
+        .exec_map(
+            &context.sql.command_xm8_preferences,
+            params! { "uid" => uid },
+            |notification_type: String| notification_type,
+        )
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    let query = replace_list(&context.sql.xm8_quiet_hours_lookup, ":uids", 1);
+
+    let quiet_hours: Option<(String, NaiveTime, NaiveTime)> = connection
+        .exec_first(&query, vec![uid])
+        .await
+        .map_err(|e| QueryError::System(format!("Query failed - {}", e)))?;
+
+    Ok(Preferences {
+        uid: uid.to_owned(),
+        disabled_types,
+        quiet_hours: quiet_hours.map(|(_, starts_at, ends_at)| QuietHours {
+            starts_at: starts_at.format("%H:%M").to_string(),
+            ends_at: ends_at.format("%H:%M").to_string(),
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+    use mysql_async::Value;
+
+    #[tokio::test]
+    async fn it_returns_the_players_preferences() {
+        let database = Fixture::database();
+
+        let mut fake = FakeExecutor::new()
+            .respond_with(
+                &database.sql.command_xm8_preferences,
+                vec![Fixture::result(&[("type", Value::from("base-raid"))])],
+            )
+            .respond_with(
+                &replace_list(&database.sql.xm8_quiet_hours_lookup, ":uids", 1),
+                vec![Fixture::result(&[
+                    ("recipient_uid", Value::from("76561198037177305")),
+                    ("starts_at", Value::Time(false, 0, 22, 0, 0, 0)),
+                    ("ends_at", Value::Time(false, 0, 7, 30, 0, 0)),
+                ])],
+            );
+
+        let arguments =
+            HashMap::from([("uid".to_string(), "76561198037177305".to_string())]);
+
+        let results = command_xm8_preferences(&database, &mut fake, &arguments)
+            .await
+            .unwrap();
+
+        let preferences: JSONValue = serde_json::from_str(&results[0]).unwrap();
+
+        assert_eq!(preferences["disabled_types"], json!(["base-raid"]));
+        assert_eq!(
+            preferences["quiet_hours"],
+            json!({"starts_at": "22:00", "ends_at": "07:30"})
+        );
+    }
+}
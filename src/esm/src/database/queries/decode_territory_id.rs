@@ -2,7 +2,7 @@ use super::*;
 
 pub async fn decode_territory_id(
     context: &Database,
-    connection: &mut Conn,
+    connection: &mut dyn Executor,
     territory_id: &str,
 ) -> Result<u64, Error> {
     // Attempt to decode the ID since this is all done in Rust
@@ -21,6 +21,23 @@ pub async fn decode_territory_id(
         )
         .await;
 
+    match result {
+        Ok(Some(v)) => return Ok(v),
+        Ok(None) => {}
+        Err(e) => return Err(e.to_string().into()),
+    }
+
+    // Custom IDs keep pointing at their territory for a while after being changed
+    let result: SQLResult<Option<u64>> = connection
+        .exec_first(
+            &context.sql.territory_custom_id_redirect,
+            params! {
+                "custom_id" => territory_id,
+                "redirect_days" => crate::CONFIG.custom_territory_id_redirect_days
+            },
+        )
+        .await;
+
     match result {
         Ok(r) => match r {
             Some(v) => Ok(v),
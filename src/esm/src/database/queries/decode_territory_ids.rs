@@ -0,0 +1,73 @@
+use super::*;
+
+/// Decodes each hashed or custom ID into its database ID, keeping the order they were
+/// provided in. IDs that don't belong to a territory are left out
+pub async fn decode_territory_ids(
+    context: &Database,
+    connection: &mut dyn Executor,
+    territory_ids: &[String],
+) -> Result<Vec<(String, u64)>, Error> {
+    let mut decoded: Vec<(String, u64)> = Vec::with_capacity(territory_ids.len());
+
+    for territory_id in territory_ids {
+        if decoded.iter().any(|(id, _)| id == territory_id) {
+            continue;
+        }
+
+        match queries::decode_territory_id(context, connection, territory_id).await {
+            Ok(database_id) => decoded.push((territory_id.to_owned(), database_id)),
+            Err(e) if e.error_content == "territory_id_does_not_exist" => continue,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+    use mysql_async::Value;
+
+    #[tokio::test]
+    async fn it_decodes_hashed_and_custom_ids() {
+        let database = Fixture::database();
+        let encoded_id = database.encode_territory_id("5");
+
+        let mut fake = FakeExecutor::new()
+            .respond_with(
+                &database.sql.check_if_territory_exists,
+                vec![Fixture::result(&[("exists", Value::from("true"))])],
+            )
+            .respond_with(
+                &database.sql.decode_territory_id,
+                vec![Fixture::result(&[("id", Value::UInt(7))])],
+            );
+
+        let territory_ids =
+            vec![encoded_id.clone(), "home".into(), encoded_id.clone()];
+
+        let decoded = decode_territory_ids(&database, &mut fake, &territory_ids)
+            .await
+            .unwrap();
+
+        assert_eq!(decoded, vec![(encoded_id, 5), ("home".into(), 7)]);
+    }
+
+    #[tokio::test]
+    async fn it_leaves_out_unknown_ids() {
+        let database = Fixture::database();
+
+        let mut fake = FakeExecutor::new()
+            .respond_with(&database.sql.decode_territory_id, vec![])
+            .respond_with(&database.sql.territory_custom_id_redirect, vec![]);
+
+        let decoded =
+            decode_territory_ids(&database, &mut fake, &["missing".to_string()])
+                .await
+                .unwrap();
+
+        assert!(decoded.is_empty());
+    }
+}
@@ -0,0 +1,16 @@
+use super::*;
+
+pub async fn end_player_session(
+    context: &Database,
+    connection: &mut dyn Executor,
+    uid: &str,
+) -> Result<(), Error> {
+    let result = connection
+        .exec_drop(&context.sql.end_player_session, params! { "uid" => uid })
+        .await;
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(e) => Err(e.to_string().into()),
+    }
+}
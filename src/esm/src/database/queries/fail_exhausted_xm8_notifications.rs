@@ -0,0 +1,44 @@
+use super::*;
+
+// Limit tampering
+fn query() -> &'static str {
+    r#"
+    UPDATE
+        xm8_notification
+    SET
+        state = "failed",
+        state_details = :state_details
+    WHERE
+        state = "pending"
+        AND acknowledged_at IS NULL
+        AND attempt_count >= :max_attempts
+        AND last_attempt_at < NOW() - INTERVAL LEAST(
+            :retry_delay * POW(2, GREATEST(attempt_count - 1, 0)),
+            :max_retry_delay
+        ) SECOND;
+    "#
+}
+
+/// Marks pending notifications as failed once they've used every attempt and the bot never
+/// acknowledged the last one. Returns the number of notifications that failed
+pub async fn fail_exhausted_xm8_notifications(
+    _context: &Database,
+    connection: &mut dyn Executor,
+) -> Result<u64, Error> {
+    let max_attempts = crate::CONFIG.xm8_notification_max_attempts;
+
+    connection
+        .execute(
+            query(),
+            params! {
+                "state_details" => format!(
+                    "Not acknowledged after {max_attempts} delivery attempts"
+                ),
+                "max_attempts" => max_attempts,
+                "retry_delay" => crate::CONFIG.xm8_notification_retry_delay_seconds,
+                "max_retry_delay" => crate::CONFIG.xm8_notification_max_retry_delay_seconds
+            },
+        )
+        .await
+        .map_err(|e| e.to_string().into())
+}
@@ -9,19 +9,25 @@ pub struct Notification {
     pub notification_type: String,
 
     pub content: String,
+
+    /// How many repeats of this notification were suppressed by deduplication or rate limiting
+    pub suppressed_count: u64,
+
     pub created_at: NaiveDateTime,
 }
 
 impl Notification {
     fn from_tuple(
-        tuple: (String, String, String, String, NaiveDateTime),
+        tuple: (String, String, String, String, u64, NaiveDateTime),
     ) -> Result<Self, String> {
         Ok(Self {
             uuids: serde_json::from_str(&tuple.0).map_err(|e| e.to_string())?,
-            recipient_uids: serde_json::from_str(&tuple.1).map_err(|e| e.to_string())?,
+            recipient_uids: serde_json::from_str(&tuple.1)
+                .map_err(|e| e.to_string())?,
             notification_type: tuple.2,
             content: tuple.3,
-            created_at: tuple.4,
+            suppressed_count: tuple.4,
+            created_at: tuple.5,
         })
     }
 }
@@ -34,31 +40,51 @@ fn query() -> &'static str {
         CONCAT('["', GROUP_CONCAT(DISTINCT recipient_uid SEPARATOR '","'), '"]') as recipient_uids,
         type,
         content,
+        suppressed_count,
         MIN(created_at) as created_at
     FROM
         xm8_notification
     WHERE
         acknowledged_at IS NULL
         AND (
-            last_attempt_at IS NULL
-            OR last_attempt_at < DATE_SUB(NOW(), INTERVAL 30 SECOND)
+            deliver_at IS NULL
+            OR deliver_at <= UTC_TIMESTAMP()
+        )
+        AND (
+            state = "new"
+            OR (
+                state = "pending"
+                AND attempt_count < :max_attempts
+                AND last_attempt_at < NOW() - INTERVAL LEAST(
+                    :retry_delay * POW(2, GREATEST(attempt_count - 1, 0)),
+                    :max_retry_delay
+                ) SECOND
+            )
         )
-        AND attempt_count < 10
     GROUP BY
-        territory_id, type, content
+        territory_id, type, content, suppressed_count
     ORDER BY
         MIN(created_at) ASC
     LIMIT
-        100;
+        :batch_size;
     "#
 }
 
 pub async fn get_xm8_notifications(
     _context: &Database,
-    connection: &mut Conn,
+    connection: &mut dyn Executor,
 ) -> Result<Vec<Notification>, Error> {
     let result = connection
-        .query_map(query(), |r| Notification::from_tuple(r))
+        .exec_map(
+            query(),
+            params! {
+                "max_attempts" => crate::CONFIG.xm8_notification_max_attempts,
+                "retry_delay" => crate::CONFIG.xm8_notification_retry_delay_seconds,
+                "max_retry_delay" => crate::CONFIG.xm8_notification_max_retry_delay_seconds,
+                "batch_size" => crate::CONFIG.xm8_notification_batch_size
+            },
+            Notification::from_tuple,
+        )
         .await;
 
     let notifications = match result {
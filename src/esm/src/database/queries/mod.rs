@@ -1,27 +1,56 @@
 use mysql_async::prelude::FromValue;
 use mysql_async::FromValueError;
 pub use mysql_async::Row;
+use mysql_async::Value;
 
 pub use crate::database::*;
 pub use crate::*;
 
 // I have this separated so Rust compiler errors will be localized to a line vs the entire macro
 import_and_export!(add_xm8_notifications);
+import_and_export!(check_if_account_exists);
 import_and_export!(check_if_territory_exists);
 import_and_export!(check_if_territory_owner);
+import_and_export!(close_player_sessions);
 import_and_export!(command_all_territories);
+import_and_export!(command_clan_info);
+import_and_export!(command_delete_territory);
+import_and_export!(command_erase_player);
+import_and_export!(command_export_player);
+import_and_export!(command_find_player);
+import_and_export!(command_integrity_check);
+import_and_export!(command_integrity_cleanup);
+import_and_export!(command_leaderboard);
 import_and_export!(command_me);
+import_and_export!(command_player_clan);
 import_and_export!(command_player_info);
+import_and_export!(command_player_sessions);
 import_and_export!(command_player_territories);
+import_and_export!(command_player_vehicles);
+import_and_export!(command_remove_clan_member);
 import_and_export!(command_reset_all);
 import_and_export!(command_reset_player);
 import_and_export!(command_restore);
 import_and_export!(command_reward);
+import_and_export!(command_set_clan_leader);
 import_and_export!(command_set_id);
+import_and_export!(command_set_xm8_preferences);
+import_and_export!(command_territories_due);
 import_and_export!(command_territory_info);
+import_and_export!(command_territory_vehicles);
+import_and_export!(command_transfer_territory);
+import_and_export!(command_transfer_vehicle);
+import_and_export!(command_xm8_notification_counts);
+import_and_export!(command_xm8_preferences);
 import_and_export!(decode_territory_id);
+import_and_export!(decode_territory_ids);
+import_and_export!(end_player_session);
+import_and_export!(fail_exhausted_xm8_notifications);
 import_and_export!(get_xm8_notifications);
+import_and_export!(purge_xm8_notifications);
+import_and_export!(queue_territory_reminders);
 import_and_export!(set_territory_payment_counter);
+import_and_export!(start_player_session);
 import_and_export!(update_xm8_attempt_counter);
 import_and_export!(update_xm8_notification_state);
 
@@ -29,21 +58,107 @@ import_and_export!(update_xm8_notification_state);
 // corresponding SQL file. These files MUST exist in @esm/sql/queries or there will be errors
 load_sql! {
     account_name_lookup,
+    add_territory_custom_id_history,
+    add_territory_reminder,
+    add_xm8_suppressed_notification,
+    check_if_account_exists,
     check_if_territory_exists,
     check_if_territory_owner,
+    clan_members_lookup,
+    clan_territories_lookup,
+    clean_territory_reminders,
+    cleanup_deleted_territory_constructions,
+    cleanup_deleted_territory_containers,
+    cleanup_orphaned_constructions,
+    cleanup_orphaned_containers,
+    cleanup_orphaned_xm8_notifications,
+    clear_xm8_quiet_hours,
+    clear_xm8_suppressed_notification,
+    close_all_player_sessions,
+    close_stale_player_sessions,
     command_all_territories,
+    command_clan_info,
+    command_delete_container,
+    command_delete_construction,
+    command_delete_territory,
+    command_delete_territory_record,
+    command_find_player,
+    command_leaderboard,
     command_me,
     command_player_info,
+    command_player_sessions,
     command_player_territories, // Used by multiple commands
+    command_player_vehicles,
+    command_remove_clan_member,
     command_reset_all,
     command_reset_player,
     command_restore_construction,
     command_restore_container,
     command_restore_territory,
+    command_restore_territory_record,
+    command_set_clan_leader,
     command_set_id,
+    command_territories_due,
     command_territory_info,
+    command_territory_vehicles,
+    command_transfer_territory,
+    command_transfer_vehicle,
+    command_xm8_preferences,
     decode_territory_id,
-    set_territory_payment_counter
+    end_player_session,
+    erase_player_account,
+    erase_player_clan_leadership,
+    erase_player_clans,
+    erase_player_constructions,
+    erase_player_containers,
+    erase_player_counts,
+    erase_player_history,
+    erase_player_player,
+    erase_player_rights,
+    erase_player_rights_lookup,
+    erase_player_sessions,
+    erase_player_shared_constructions,
+    erase_player_shared_containers,
+    erase_player_territories,
+    erase_player_vehicles,
+    erase_player_xm8_notifications,
+    erase_player_xm8_preferences,
+    erase_player_xm8_quiet_hours,
+    erase_player_xm8_suppressed_notifications,
+    export_player_account,
+    export_player_constructions,
+    export_player_containers,
+    export_player_history,
+    export_player_player,
+    export_player_sessions,
+    export_player_territories,
+    export_player_vehicles,
+    export_player_xm8_notifications,
+    export_player_xm8_preferences,
+    export_player_xm8_quiet_hours,
+    export_player_xm8_suppressed_notifications,
+    integrity_dead_players,
+    integrity_orphaned_constructions,
+    integrity_orphaned_containers,
+    integrity_orphaned_xm8_notifications,
+    merge_xm8_notification,
+    player_clan_lookup,
+    player_sessions_lookup,
+    set_territory_payment_counter,
+    set_xm8_preference,
+    set_xm8_quiet_hours,
+    start_player_session,
+    territory_custom_id_cooldown,
+    territory_custom_id_redirect,
+    territory_deletion_lookup,
+    territory_ownership_lookup,
+    territory_reminder_lookup,
+    vehicle_owner_lookup,
+    xm8_disabled_recipients_lookup,
+    xm8_quiet_hours_lookup,
+    xm8_recent_counts_lookup,
+    xm8_recent_duplicates_lookup,
+    xm8_suppressed_lookup
 }
 
 pub fn select_column<T>(row: &Row, index: &str) -> Result<T, String>
@@ -55,6 +170,29 @@ where
         .and_then(|v| v.map_err(|e: FromValueError| e.to_string()))
 }
 
+/// Converts a column value to JSON. Used by command_all_territories to store the sort value
+/// in the cursor and by command_export_player
+pub fn json_value(value: &Value) -> JSONValue {
+    match value {
+        Value::Int(n) => json!(n),
+        Value::UInt(n) => json!(n),
+        Value::Float(n) => json!(n),
+        Value::Double(n) => json!(n),
+        Value::Bytes(bytes) => json!(String::from_utf8_lossy(bytes)),
+        Value::Date(year, month, day, hour, minute, second, _) => json!(format!(
+            "{year:04}-{month:02}-{day:02} {hour:02}:{minute:02}:{second:02}"
+        )),
+        // TIME columns. MySQL allows these to be negative or longer than a day
+        Value::Time(negative, days, hours, minutes, seconds, _) => {
+            let hours = *days * 24 + u32::from(*hours);
+            let sign = if *negative { "-" } else { "" };
+
+            json!(format!("{sign}{hours:02}:{minutes:02}:{seconds:02}"))
+        }
+        _ => JSONValue::Null,
+    }
+}
+
 pub fn replace_list(query: &str, placeholder: &str, quantity: usize) -> String {
     // Annoying workaround for `IN` query, or insert multiple
     let placeholders = vec!["?"; quantity].join(",");
@@ -0,0 +1,128 @@
+use super::*;
+
+use std::collections::BTreeMap;
+
+// Small batches keep each delete from holding locks long enough to stall the poller
+const BATCH_SIZE: u64 = 1000;
+
+// Limit tampering
+fn query() -> &'static str {
+    r#"
+    DELETE FROM
+        xm8_notification
+    WHERE
+        state = :state
+        AND COALESCE(acknowledged_at, last_attempt_at, created_at) < NOW() - INTERVAL :retention_days DAY
+    ORDER BY
+        id
+    LIMIT
+        :batch_size;
+    "#
+}
+
+/// Deletes notifications that have been in a state longer than its retention. Time in the
+/// state is measured from the last thing that happened to the notification.
+/// Returns the number of notifications deleted for each state
+pub async fn purge_xm8_notifications(
+    _context: &Database,
+    connection: &mut dyn Executor,
+    retention_days: &HashMap<String, u64>,
+) -> Result<BTreeMap<String, u64>, Error> {
+    let mut purged = BTreeMap::new();
+
+    for (state, days) in retention_days {
+        // Zero keeps the state forever
+        if *days == 0 {
+            continue;
+        }
+
+        let mut total = 0;
+        loop {
+            let deleted = connection
+                .execute(
+                    query(),
+                    params! {
+                        "state" => state,
+                        "retention_days" => days,
+                        "batch_size" => BATCH_SIZE
+                    },
+                )
+                .await
+                .map_err(|e| e.to_string())?;
+
+            total += deleted;
+
+            if deleted < BATCH_SIZE {
+                break;
+            }
+        }
+
+        purged.insert(state.to_owned(), total);
+    }
+
+    Ok(purged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reports the given number of deleted rows for each delete, in order
+    struct DeletingExecutor {
+        deleted: Vec<u64>,
+        statements: usize,
+    }
+
+    impl Executor for DeletingExecutor {
+        fn fetch<'a>(
+            &'a mut self,
+            _statement: &'a str,
+            _params: Params,
+        ) -> ExecutorFuture<'a, Vec<Row>> {
+            unreachable!("The purge doesn't read")
+        }
+
+        fn execute<'a>(
+            &'a mut self,
+            _statement: &'a str,
+            _params: Params,
+        ) -> ExecutorFuture<'a, u64> {
+            let deleted = self.deleted.get(self.statements).copied().unwrap_or(0);
+            self.statements += 1;
+
+            Box::pin(async move { Ok(deleted) })
+        }
+
+        fn execute_batch<'a>(
+            &'a mut self,
+            _statement: &'a str,
+            _params: Vec<Params>,
+        ) -> ExecutorFuture<'a, ()> {
+            unreachable!("The purge doesn't batch")
+        }
+    }
+
+    #[tokio::test]
+    async fn it_deletes_in_batches_until_a_batch_comes_up_short() {
+        let database = crate::database::fake::Fixture::database();
+        let mut executor = DeletingExecutor {
+            deleted: vec![BATCH_SIZE, BATCH_SIZE, 12],
+            statements: 0,
+        };
+
+        let purged = purge_xm8_notifications(
+            &database,
+            &mut executor,
+            &HashMap::from([("sent".to_string(), 7), ("failed".to_string(), 0)]),
+        )
+        .await
+        .unwrap();
+
+        // Failed notifications are kept forever, so they are never deleted
+        assert_eq!(
+            purged,
+            BTreeMap::from([("sent".to_string(), BATCH_SIZE * 2 + 12)])
+        );
+        assert_eq!(executor.statements, 3);
+    }
+}
@@ -0,0 +1,171 @@
+use super::*;
+
+/// Enqueues `protection-money-due` XM8 notifications for territories that are within one of
+/// the lead times. Each territory is reminded once per lead time, per payment cycle.
+/// Does nothing until the server sends its territory lifetime on init.
+/// Returns the number of territories that were reminded
+pub async fn queue_territory_reminders(
+    context: &Database,
+    connection: &mut dyn Executor,
+    lead_hours: &[u64],
+) -> Result<usize, Error> {
+    let Some(max_lead_hours) = lead_hours.iter().max() else {
+        return Ok(0);
+    };
+
+    if crate::bot::territory_lifetime() == 0 {
+        return Ok(0);
+    }
+
+    connection
+        .query_drop(&context.sql.clean_territory_reminders)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let territories =
+        queries::find_territories_due(context, connection, *max_lead_hours)
+            .await
+            .map_err(|e| match e {
+                QueryError::System(e)
+                | QueryError::User(e)
+                | QueryError::Code(e) => e,
+            })?;
+
+    let mut reminded = 0;
+    for territory in territories {
+        let Some(lead_time_hours) =
+            applicable_lead_time(lead_hours, territory.minutes_remaining)
+        else {
+            continue;
+        };
+
+        // One territory's failure shouldn't hold up the others. It is retried next time
+        match remind_territory(context, connection, &territory, lead_time_hours)
+            .await
+        {
+            Ok(true) => reminded += 1,
+            Ok(false) => {}
+            Err(e) => warn!(
+                "[queue_territory_reminders] Failed to remind territory {}. {}",
+                territory.database_id, e.error_content
+            ),
+        }
+    }
+
+    Ok(reminded)
+}
+
+/// Queues the reminder and records it. The reminder is recorded only after the
+/// notification is queued so a failure doesn't stop it from being retried.
+/// Returns false if the territory was already reminded for this lead time
+async fn remind_territory(
+    context: &Database,
+    connection: &mut dyn Executor,
+    territory: &DueTerritory,
+    lead_time_hours: u64,
+) -> Result<bool, Error> {
+    // The primary key covers the territory, lead time, and due date. Paying the territory
+    // moves the due date, which starts a new cycle
+    let reminder = params! {
+        "territory_id" => territory.database_id,
+        "lead_time_hours" => lead_time_hours,
+        "due_at" => territory.due_at
+    };
+
+    let reminded: Option<bool> = connection
+        .exec_first(&context.sql.territory_reminder_lookup, reminder.clone())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if reminded.unwrap_or_default() {
+        return Ok(false);
+    }
+
+    queries::add_xm8_notifications(
+        context,
+        connection,
+        NotificationType::ProtectionMoneyDue,
+        serde_json::to_string(&territory.build_rights).unwrap(),
+        HashMap::from([
+            ("territory_id".into(), territory.database_id.to_string()),
+            ("territory_name".into(), territory.name.to_owned()),
+        ]),
+    )
+    .await?;
+
+    connection
+        .execute(&context.sql.add_territory_reminder, reminder)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(true)
+}
+
+/// The smallest lead time the territory falls within. A territory that first shows up inside
+/// several lead times is only reminded once, and the larger lead times never fire after it
+fn applicable_lead_time(lead_hours: &[u64], minutes_remaining: i64) -> Option<u64> {
+    lead_hours
+        .iter()
+        .filter(|hours| (**hours as i64) * 60 >= minutes_remaining)
+        .min()
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+    use mysql_async::Value;
+
+    #[test]
+    fn it_picks_the_smallest_lead_time() {
+        let lead_hours = [72, 24];
+
+        assert_eq!(applicable_lead_time(&lead_hours, 60 * 48), Some(72));
+        assert_eq!(applicable_lead_time(&lead_hours, 60 * 12), Some(24));
+        assert_eq!(applicable_lead_time(&lead_hours, 60 * 24), Some(24));
+        assert_eq!(applicable_lead_time(&lead_hours, 60 * 96), None);
+    }
+
+    #[tokio::test]
+    async fn it_waits_for_the_territory_lifetime() {
+        let database = Fixture::database();
+        let mut fake = FakeExecutor::new();
+
+        // The server hasn't sent init in tests, so the lifetime is unknown
+        let result = queue_territory_reminders(&database, &mut fake, &[24]).await;
+
+        assert!(matches!(result, Ok(0)));
+        assert!(fake.statements.is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_skips_territories_that_fail_validation() {
+        let database = Fixture::database();
+        let mut fake = FakeExecutor::new().respond_with(
+            &database.sql.territory_reminder_lookup,
+            vec![Fixture::result(&[("reminded", Value::Int(0))])],
+        );
+
+        let territory = DueTerritory {
+            id: database.encode_territory_id("5"),
+            database_id: 5,
+            name: "".into(),
+            owner_uid: "76561198037177305".into(),
+            owner_name: "Bryan".into(),
+            build_rights: vec!["76561198037177305".into()],
+            last_paid_at: NaiveDateTime::default(),
+            due_at: NaiveDateTime::default(),
+            minutes_remaining: 60,
+        };
+
+        let result = remind_territory(&database, &mut fake, &territory, 24).await;
+
+        // Nothing is recorded, so the reminder is tried again once the name is fixed
+        assert!(result.is_err());
+        assert_eq!(
+            fake.statements,
+            vec![database.sql.territory_reminder_lookup]
+        );
+    }
+}
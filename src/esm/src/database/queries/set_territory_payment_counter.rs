@@ -2,7 +2,7 @@ use super::*;
 
 pub async fn set_territory_payment_counter(
     context: &Database,
-    connection: &mut Conn,
+    connection: &mut dyn Executor,
     database_id: usize,
     counter_value: usize,
 ) -> Result<(), Error> {
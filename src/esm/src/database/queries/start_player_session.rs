@@ -0,0 +1,62 @@
+use super::*;
+
+/// Returns false if the player's account doesn't exist yet. Exile creates it with a fire and
+/// forget query right before the player connects, so a new player's account can lag behind
+pub async fn start_player_session(
+    context: &Database,
+    connection: &mut dyn Executor,
+    uid: &str,
+    name: &str,
+) -> Result<bool, Error> {
+    // The player can't be connected twice. Any open session was left behind by a server crash
+    // and its real end is unknown, so it is closed without counting towards playtime
+    connection
+        .exec_drop(
+            &context.sql.close_stale_player_sessions,
+            params! { "uid" => uid },
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let result = connection
+        .execute(
+            &context.sql.start_player_session,
+            params! {
+                "uid" => uid,
+                "name" => name
+            },
+        )
+        .await;
+
+    match result {
+        Ok(rows) => Ok(rows > 0),
+        Err(e) => Err(e.to_string().into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::fake::{FakeExecutor, Fixture};
+
+    #[tokio::test]
+    async fn it_waits_for_the_account() {
+        let database = Fixture::database();
+
+        for (rows, started) in [(0, false), (1, true)] {
+            let mut fake = FakeExecutor::new()
+                .affect(&database.sql.close_stale_player_sessions, 0)
+                .affect(&database.sql.start_player_session, rows);
+
+            let result = start_player_session(
+                &database,
+                &mut fake,
+                "76561198037177305",
+                "Bryan",
+            )
+            .await;
+
+            assert_eq!(result.unwrap(), started);
+        }
+    }
+}
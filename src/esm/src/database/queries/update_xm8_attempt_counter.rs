@@ -9,15 +9,17 @@ fn query() -> &'static str {
         state = "pending",
         state_details = "attempting delivery",
         attempt_count = attempt_count + 1,
-        last_attempt_at = CURRENT_TIME()
+        last_attempt_at = NOW()
     WHERE
-        uuid IN (:uuids);
+        uuid IN (:uuids)
+        AND state IN ("new", "pending");
     "#
 }
 
+/// Moves the notifications from new to pending, or records another attempt for pending ones
 pub async fn update_xm8_attempt_counter(
     _context: &Database,
-    connection: &mut Conn,
+    connection: &mut dyn Executor,
     uuids: Vec<&String>,
 ) -> Result<(), Error> {
     let query = replace_list(query(), ":uuids", uuids.len());
@@ -14,22 +14,35 @@ fn query() -> &'static str {
     SET
         state = :state,
         state_details = :state_details,
-        acknowledged_at = CURRENT_TIME()
+        acknowledged_at = NOW()
     WHERE
-        uuid = :uuid;
+        uuid = :uuid
+        AND state = "pending";
     "#
 }
 
+/// The bot reports whether a pending notification was delivered. Only pending notifications
+/// can move to sent or failed. Anything else is ignored
 pub async fn update_xm8_notification_state(
     _context: &Database,
-    connection: &mut Conn,
+    connection: &mut dyn Executor,
     state_by_uuid: HashMap<String, JSONValue>,
 ) -> Result<(), QueryError> {
     let state_by_uuid: HashMap<String, NotificationState> = state_by_uuid
         .into_iter()
         .filter_map(|(key, value)| {
             match serde_json::from_value::<NotificationState>(value.to_owned()) {
-                Ok(state) => Some((key, state)),
+                Ok(state) if ["sent", "failed"].contains(&state.state.as_str()) => {
+                    Some((key, state))
+                }
+                Ok(state) => {
+                    warn!(
+                        "[update_xm8_notification_state] ⚠ Ignoring {key}. \"{}\" is not a valid state",
+                        state.state
+                    );
+
+                    None
+                }
                 Err(_) => None,
             }
         })
@@ -15,6 +15,8 @@ pub fn add_xm8_notification(
         content
     );
 
+    let notification_type: NotificationType = notification_type.parse()?;
+
     let content: HashMap<String, JSONValue> = match Parser::from_arma(&content) {
         Ok(d) => d,
         Err(e) => return Err(e.into()),
@@ -39,6 +41,10 @@ pub fn add_xm8_notification(
             .map_err(|e| e.error_content)
     });
 
+    if result.is_ok() {
+        crate::bot::wake_xm8_notification_thread();
+    }
+
     debug!("[add_xm8_notification] ⏲ Took {:.2?}", timer.elapsed());
 
     result
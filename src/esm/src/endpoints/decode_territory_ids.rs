@@ -0,0 +1,24 @@
+use parser::Parser;
+
+use super::*;
+
+/// Decodes many hashed or custom territory IDs at once. Returns
+/// [[territory_id, database_id], ...], which SQF converts into a hashmap.
+/// IDs that don't belong to a territory are left out
+pub fn decode_territory_ids(territory_ids: String) -> Result<String, String> {
+    let timer = std::time::Instant::now();
+    trace!("[decode_territory_ids] territory_ids: {territory_ids}");
+
+    let territory_ids: Vec<String> = Parser::from_arma(&territory_ids)?;
+
+    let result = TOKIO_RUNTIME.block_on(async {
+        DATABASE
+            .decode_territory_ids(&territory_ids)
+            .await
+            .map_err(|e| e.error_content)
+    });
+
+    debug!("[decode_territory_ids] ⏲ Took {:.2?}", timer.elapsed());
+
+    serde_json::to_string(&result?).map_err(|e| e.to_string())
+}
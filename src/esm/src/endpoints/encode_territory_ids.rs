@@ -0,0 +1,28 @@
+use parser::Parser;
+
+use super::*;
+
+/// Encodes many database IDs at once. Returns [[database_id, encoded_id], ...], which SQF
+/// converts into a hashmap. IDs that aren't numbers are left out
+pub fn encode_territory_ids(database_ids: String) -> Result<String, String> {
+    trace!("[encode_territory_ids] database_ids: {database_ids}");
+
+    // Convert the database Ids from "['1','2']" to ["1","2"]
+    let database_ids: Vec<String> = Parser::from_arma(&database_ids)?;
+
+    let mut encoded_ids: Vec<(String, String)> = Vec::new();
+    for database_id in database_ids {
+        if encoded_ids.iter().any(|(id, _)| *id == database_id) {
+            continue;
+        }
+
+        let encoded_id = DATABASE.hasher.encode(&database_id);
+        if encoded_id.is_empty() {
+            continue;
+        }
+
+        encoded_ids.push((database_id, encoded_id));
+    }
+
+    serde_json::to_string(&encoded_ids).map_err(|e| e.to_string())
+}
@@ -1,11 +1,15 @@
 use crate::*;
 
 import!(add_xm8_notification);
+import!(decode_territory_ids);
 import!(encode_territory_id);
+import!(encode_territory_ids);
 import!(log_level);
 import!(log_output);
 import!(log);
 import!(number_to_string);
+import!(player_connected);
+import!(player_disconnected);
 import!(pre_init);
 import!(send_message);
 import!(send_to_channel);
@@ -20,11 +24,15 @@ pub fn register() -> Extension {
             std::include_str!("../../.build-sha")
         ))
         .command("encode_territory_id", encode_territory_id)
+        .command("encode_territory_ids", encode_territory_ids)
+        .command("decode_territory_ids", decode_territory_ids)
         .command("add_xm8_notification", add_xm8_notification)
         .command("log_level", log_level)
         .command("log_output", log_output)
         .command("log", log)
         .command("number_to_string", number_to_string)
+        .command("player_connected", player_connected)
+        .command("player_disconnected", player_disconnected)
         .command("pre_init", pre_init)
         .command("send_message", send_message)
         .command("send_to_channel", send_to_channel)
@@ -0,0 +1,21 @@
+use super::*;
+
+pub fn player_connected(uid: String, name: String) -> Result<(), String> {
+    let timer = std::time::Instant::now();
+    trace!("[player_connected] uid: {:?} - name: {:?}", uid, name);
+
+    if uid.trim().is_empty() {
+        return Err("No UID provided".into());
+    }
+
+    // The session may wait on Exile to create the player's account. Don't hold up the server
+    TOKIO_RUNTIME.spawn(async move {
+        if let Err(e) = DATABASE.start_player_session(&uid, &name).await {
+            error!("[player_connected] ❌ {}", e.error_content);
+        }
+
+        debug!("[player_connected] ⏲ Took {:.2?}", timer.elapsed());
+    });
+
+    Ok(())
+}
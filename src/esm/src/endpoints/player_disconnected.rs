@@ -0,0 +1,21 @@
+use super::*;
+
+pub fn player_disconnected(uid: String) -> Result<(), String> {
+    let timer = std::time::Instant::now();
+    trace!("[player_disconnected] uid: {:?}", uid);
+
+    if uid.trim().is_empty() {
+        return Err("No UID provided".into());
+    }
+
+    let result = TOKIO_RUNTIME.block_on(async {
+        DATABASE
+            .end_player_session(&uid)
+            .await
+            .map_err(|e| e.error_content)
+    });
+
+    debug!("[player_disconnected] ⏲ Took {:.2?}", timer.elapsed());
+
+    result
+}
@@ -32,6 +32,7 @@ mod parser;
 mod request;
 mod router;
 mod token;
+mod xm8;
 
 pub use arma::DATABASE;
 pub use bot::TOKEN_MANAGER;
@@ -40,6 +41,7 @@ pub use error::*;
 pub use message::*;
 pub use request::*;
 pub use router::ROUTER;
+pub use xm8::*;
 
 pub type ESMResult = Result<(), Error>;
 pub type MessageResult = Result<Option<Message>, Error>;
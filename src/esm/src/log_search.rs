@@ -1,11 +1,77 @@
+use crate::message::Data;
 use chrono::{DateTime, Duration, Utc};
 use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JSONValue;
+use std::collections::VecDeque;
 use std::path::{Path, PathBuf};
 use tokio::fs::{self, File};
 use tokio::io::{AsyncBufReadExt, BufReader};
 
-pub async fn search_files(search: &str) -> Result<Vec<MatchResult>, String> {
+/// How much a single search is allowed to return
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchOptions {
+    pub limit: usize,
+    pub per_file_limit: usize,
+    pub context_before: usize,
+    pub context_after: usize,
+    pub chunk_size: usize,
+}
+
+impl SearchOptions {
+    /// Reads `limit`, `context_before`, and `context_after` from the search request.
+    /// Each is capped by the log_search settings in @esm/config.yml
+    pub fn from_data(data: &Data) -> Result<Self, String> {
+        let config = &crate::CONFIG;
+
+        let read = |key: &str, default: usize| -> Result<usize, String> {
+            let Some(value) = data.get(key) else {
+                return Ok(default);
+            };
+
+            let number = match value {
+                JSONValue::String(s) => s.parse::<usize>().ok(),
+                v => v.as_u64().map(|n| n as usize),
+            };
+
+            number.ok_or_else(|| {
+                format!("`{key}` must be a positive number, got {value}")
+            })
+        };
+
+        let max_context = config.log_search_max_context_lines;
+
+        Ok(Self {
+            limit: read("limit", config.log_search_max_results)?
+                .clamp(1, config.log_search_max_results),
+            per_file_limit: config.log_search_max_results_per_file,
+            context_before: read("context_before", 0)?.min(max_context),
+            context_after: read("context_after", 0)?.min(max_context),
+            chunk_size: config.log_search_chunk_size,
+        })
+    }
+}
+
+/// Describes a finished search. Sent with the final chunk of results
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct SearchSummary {
+    pub total_results: usize,
+    pub files_searched: usize,
+    // True when there were more matches than the limits allowed
+    pub truncated: bool,
+    pub truncated_files: Vec<String>,
+}
+
+/// Searches every log file, handing results to `on_chunk` as soon as `chunk_size` of
+/// them are found so they never have to be held all at once
+pub async fn search_files<F>(
+    search: &str,
+    options: SearchOptions,
+    mut on_chunk: F,
+) -> Result<SearchSummary, String>
+where
+    F: FnMut(Vec<MatchResult>) -> Result<(), String>,
+{
     let pattern = RegexBuilder::new(search)
         .case_insensitive(true)
         .build()
@@ -23,13 +89,47 @@ pub async fn search_files(search: &str) -> Result<Vec<MatchResult>, String> {
         .chain(log_finder.get_additional_log_paths().await)
         .collect();
 
-    let mut results: Vec<MatchResult> = vec![];
+    let mut summary = SearchSummary::default();
+    let mut chunk: Vec<MatchResult> = vec![];
+
     for path in paths {
-        let paths = search_file(path, &pattern).await?;
-        results.extend(paths);
+        // Once the limit is reached, files are only searched to see if there were more
+        let remaining = options.limit - summary.total_results;
+
+        let file_name = path.display().to_string();
+        let limit = remaining.min(options.per_file_limit);
+        let file_search = search_file(path, &pattern, &options, limit).await?;
+
+        summary.files_searched += 1;
+        summary.total_results += file_search.matches.len();
+
+        if file_search.truncated {
+            summary.truncated = true;
+
+            // Hitting the overall limit isn't a problem with this file
+            if limit == options.per_file_limit {
+                summary.truncated_files.push(file_name);
+            }
+        }
+
+        for result in file_search.matches {
+            chunk.push(result);
+
+            if chunk.len() >= options.chunk_size {
+                on_chunk(std::mem::take(&mut chunk))?;
+            }
+        }
+
+        if summary.truncated && summary.total_results == options.limit {
+            break;
+        }
     }
 
-    Ok(results)
+    if !chunk.is_empty() {
+        on_chunk(chunk)?;
+    }
+
+    Ok(summary)
 }
 
 pub struct LogFinder<'a> {
@@ -144,12 +244,27 @@ pub struct MatchResult {
     file_name: String,
     line_number: usize,
     content: String,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    context_before: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    context_after: Vec<String>,
+}
+
+#[derive(Debug)]
+struct FileSearch {
+    matches: Vec<MatchResult>,
+    // There was at least one more match after the limit was reached
+    truncated: bool,
 }
 
 async fn search_file(
     path: PathBuf,
     pattern: &Regex,
-) -> Result<Vec<MatchResult>, String> {
+    options: &SearchOptions,
+    limit: usize,
+) -> Result<FileSearch, String> {
     // Check if file exists
     if !tokio::fs::try_exists(&path)
         .await
@@ -164,7 +279,10 @@ async fn search_file(
 
     let mut reader = BufReader::new(file);
     let mut line = Vec::new();
-    let mut matches = Vec::new();
+    let mut matches: Vec<MatchResult> = Vec::new();
+    let mut previous_lines: VecDeque<String> =
+        VecDeque::with_capacity(options.context_before);
+    let mut truncated = false;
     let mut line_number = 1;
 
     loop {
@@ -179,20 +297,52 @@ async fn search_file(
             break;
         }
 
-        let line_text = String::from_utf8_lossy(&line).into_owned();
+        let raw_line = String::from_utf8_lossy(&line);
+        let line_text = raw_line.trim().to_string();
 
-        if pattern.is_match(&line_text) {
+        // Matches near the end of the results still need their following lines
+        for result in matches.iter_mut().rev() {
+            if result.line_number + options.context_after < line_number {
+                break;
+            }
+
+            result.context_after.push(line_text.to_owned());
+        }
+
+        let collecting_context = matches.last().is_some_and(|last| {
+            last.line_number + options.context_after > line_number
+        });
+
+        if truncated || matches.len() == limit {
+            if truncated || pattern.is_match(&raw_line) {
+                truncated = true;
+
+                if !collecting_context {
+                    break;
+                }
+            }
+        } else if pattern.is_match(&raw_line) {
             matches.push(MatchResult {
                 file_name: path.display().to_string(),
                 line_number,
-                content: line_text.trim().to_string(),
+                content: line_text.to_owned(),
+                context_before: previous_lines.iter().cloned().collect(),
+                context_after: vec![],
             });
         }
 
+        if options.context_before > 0 {
+            if previous_lines.len() == options.context_before {
+                previous_lines.pop_front();
+            }
+
+            previous_lines.push_back(line_text);
+        }
+
         line_number += 1;
     }
 
-    Ok(matches)
+    Ok(FileSearch { matches, truncated })
 }
 
 #[cfg(test)]
@@ -201,6 +351,16 @@ mod tests {
     use tempfile::NamedTempFile;
     use tokio::fs::write;
 
+    fn options() -> SearchOptions {
+        SearchOptions {
+            limit: 500,
+            per_file_limit: 100,
+            context_before: 0,
+            context_after: 0,
+            chunk_size: 50,
+        }
+    }
+
     #[tokio::test]
     async fn test_file_not_found() {
         let path = PathBuf::from("nonexistent.txt");
@@ -209,7 +369,7 @@ mod tests {
             .build()
             .unwrap();
 
-        let result = search_file(path, &pattern).await;
+        let result = search_file(path, &pattern, &options(), 100).await;
 
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("does not exist"));
@@ -227,9 +387,11 @@ mod tests {
             .build()
             .unwrap();
 
-        let results = search_file(file.path().to_path_buf(), &pattern)
-            .await
-            .unwrap();
+        let results =
+            search_file(file.path().to_path_buf(), &pattern, &options(), 100)
+                .await
+                .unwrap()
+                .matches;
 
         assert_eq!(results.len(), 2);
         assert_eq!(results[0].line_number, 1);
@@ -237,4 +399,60 @@ mod tests {
         assert_eq!(results[1].line_number, 3);
         assert_eq!(results[1].content, "test2");
     }
+
+    #[tokio::test]
+    async fn test_context_lines() {
+        let file = NamedTempFile::new().unwrap();
+        write(file.path(), "one\ntwo\ntest1\nthree\ntest2\nfour\n")
+            .await
+            .unwrap();
+
+        let pattern = RegexBuilder::new(r"test\d").build().unwrap();
+        let options = SearchOptions {
+            context_before: 2,
+            context_after: 1,
+            ..options()
+        };
+
+        let results =
+            search_file(file.path().to_path_buf(), &pattern, &options, 100)
+                .await
+                .unwrap()
+                .matches;
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].context_before, vec!["one", "two"]);
+        assert_eq!(results[0].context_after, vec!["three"]);
+        assert_eq!(results[1].context_before, vec!["test1", "three"]);
+        assert_eq!(results[1].context_after, vec!["four"]);
+    }
+
+    #[tokio::test]
+    async fn test_limit() {
+        let file = NamedTempFile::new().unwrap();
+        write(file.path(), "test1\ntest2\nno match\ntest3\n")
+            .await
+            .unwrap();
+
+        let pattern = RegexBuilder::new(r"test\d").build().unwrap();
+        let options = SearchOptions {
+            context_after: 1,
+            ..options()
+        };
+
+        let search = search_file(file.path().to_path_buf(), &pattern, &options, 1)
+            .await
+            .unwrap();
+
+        assert!(search.truncated);
+        assert_eq!(search.matches.len(), 1);
+        assert_eq!(search.matches[0].context_after, vec!["test2"]);
+
+        let search = search_file(file.path().to_path_buf(), &pattern, &options, 3)
+            .await
+            .unwrap();
+
+        assert!(!search.truncated);
+        assert_eq!(search.matches.len(), 3);
+    }
 }
@@ -80,6 +80,18 @@ macro_rules! load_sql {
                 }
             }
 
+            #[cfg(test)]
+            pub fn from_directory(directory: &std::path::Path) -> Self {
+                Queries {
+                    $(
+                        $names: std::fs::read_to_string(
+                            directory.join(concat!(stringify!($names), ".sql"))
+                        )
+                        .unwrap_or_default()
+                    ),*
+                }
+            }
+
             pub fn validate(&self) -> ESMResult {
                 $(
                     if self.$names.is_empty() {
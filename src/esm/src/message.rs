@@ -177,6 +177,8 @@ pub enum Type {
     Call,
     Query,
     Search,
+    // One chunk of a streamed search's results. Not an acknowledgement
+    SearchChunk,
 }
 
 ////////////////////////////////////////////////////////////
@@ -208,6 +210,16 @@ mod tests {
         assert!(message.errors.is_empty());
     }
 
+    #[test]
+    fn test_serializing_search_chunk() {
+        let message = Message::new().set_type(Type::SearchChunk);
+        let json = serde_json::to_string(&message).unwrap();
+
+        let expected =
+            format!("{{\"id\":\"{}\",\"type\":\"search_chunk\"}}", message.id);
+        assert_eq!(json, expected);
+    }
+
     #[test]
     fn test_from_str() {
         let id = Uuid::new_v4();
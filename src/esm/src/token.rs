@@ -1,6 +1,6 @@
 use std::{fs::File, io::Read};
 
-use crate::{arma::DATABASE, *};
+use crate::{arma::DATABASE, database::hasher::Salt, *};
 use serde::{Deserialize, Serialize};
 
 /// Represents the esm.key file
@@ -110,6 +110,12 @@ impl TokenManager {
             Ok(token) => {
                 self.token.update_from(token);
                 debug!("[load] Token loaded - {}", self.token);
+
+                // Kept next to esm.key, wherever it was found
+                if let Some(directory) = path.parent() {
+                    self.update_hasher(directory);
+                }
+
                 Ok(())
             }
             Err(e) => {
@@ -138,9 +144,56 @@ impl TokenManager {
             Err(e) => error!("[reload] ❌ {}", e),
         }
 
-        DATABASE.hasher.set_salt(&self.token.secret);
-
         info!("[reload] ✅ Token was reloaded");
         self
     }
+
+    /// Salts territory IDs with the secret. The salts from previous secrets are saved next to
+    /// esm.key so territory IDs that players saved before a key change still work
+    fn update_hasher(&self, directory: &std::path::Path) {
+        if !self.token.valid() {
+            return;
+        }
+
+        let path = directory.join("territory_salts.json");
+
+        let saved = match std::fs::read(&path) {
+            Ok(contents) => serde_json::from_slice::<Vec<Salt>>(&contents)
+                .map(Some)
+                .map_err(|e| e.to_string()),
+
+            // The file won't exist until the first time the secret is set
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.to_string()),
+        };
+
+        let save = match saved {
+            Ok(Some(salts)) => {
+                DATABASE.hasher.restore(salts);
+                true
+            }
+            Ok(None) => true,
+            Err(e) => {
+                error!("[update_hasher] ❌ Failed to read {path:?}. It will not be updated until it is fixed. {e}");
+                false
+            }
+        };
+
+        DATABASE.hasher.set_secret(&self.token.secret);
+
+        // Saving now would replace every previous salt with just this one
+        if !save {
+            return;
+        }
+
+        let result = serde_json::to_vec(&DATABASE.hasher.salts())
+            .map_err(|e| e.to_string())
+            .and_then(|contents| {
+                std::fs::write(&path, contents).map_err(|e| e.to_string())
+            });
+
+        if let Err(e) = result {
+            error!("[update_hasher] ❌ Failed to save {path:?}. {e}");
+        }
+    }
 }
@@ -0,0 +1,230 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// The XM8 notification types that the bot knows how to render
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotificationType {
+    BaseRaid,
+    ChargePlantStarted,
+    Custom,
+    FlagRestored,
+    FlagStealStarted,
+    FlagStolen,
+    GrindStarted,
+    HackStarted,
+    MarxetItemSold,
+    ProtectionMoneyDue,
+    ProtectionMoneyPaid,
+    TerritoryTransferred,
+}
+
+const TERRITORY_FIELDS: &[&str] = &["territory_id", "territory_name"];
+
+impl NotificationType {
+    pub const ALL: [NotificationType; 12] = [
+        NotificationType::BaseRaid,
+        NotificationType::ChargePlantStarted,
+        NotificationType::Custom,
+        NotificationType::FlagRestored,
+        NotificationType::FlagStealStarted,
+        NotificationType::FlagStolen,
+        NotificationType::GrindStarted,
+        NotificationType::HackStarted,
+        NotificationType::MarxetItemSold,
+        NotificationType::ProtectionMoneyDue,
+        NotificationType::ProtectionMoneyPaid,
+        NotificationType::TerritoryTransferred,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationType::BaseRaid => "base-raid",
+            NotificationType::ChargePlantStarted => "charge-plant-started",
+            NotificationType::Custom => "custom",
+            NotificationType::FlagRestored => "flag-restored",
+            NotificationType::FlagStealStarted => "flag-steal-started",
+            NotificationType::FlagStolen => "flag-stolen",
+            NotificationType::GrindStarted => "grind-started",
+            NotificationType::HackStarted => "hack-started",
+            NotificationType::MarxetItemSold => "marxet-item-sold",
+            NotificationType::ProtectionMoneyDue => "protection-money-due",
+            NotificationType::ProtectionMoneyPaid => "protection-money-paid",
+            NotificationType::TerritoryTransferred => "territory-transferred",
+        }
+    }
+
+    /// The content keys that must be provided and cannot be empty
+    pub fn required_fields(&self) -> &'static [&'static str] {
+        match self {
+            NotificationType::BaseRaid
+            | NotificationType::ChargePlantStarted
+            | NotificationType::FlagRestored
+            | NotificationType::FlagStealStarted
+            | NotificationType::FlagStolen
+            | NotificationType::GrindStarted
+            | NotificationType::HackStarted
+            | NotificationType::ProtectionMoneyDue
+            | NotificationType::ProtectionMoneyPaid => TERRITORY_FIELDS,
+            NotificationType::MarxetItemSold => &["item_name", "poptabs_received"],
+            NotificationType::TerritoryTransferred => &[
+                "territory_id",
+                "territory_name",
+                "previous_owner_uid",
+                "new_owner_uid",
+            ],
+            // Needs a title or a description, which is checked in `validate`
+            NotificationType::Custom => &[],
+        }
+    }
+
+    /// Checks the content against this type's schema and reports every problem
+    pub fn validate(&self, content: &HashMap<String, String>) -> Result<(), String> {
+        let mut problems: Vec<String> = self
+            .required_fields()
+            .iter()
+            .filter(|field| {
+                content
+                    .get(**field)
+                    .map(|value| value.trim().is_empty())
+                    .unwrap_or(true)
+            })
+            .map(|field| format!("missing `{field}`"))
+            .collect();
+
+        if let Some(id) = content.get("territory_id") {
+            if !id.is_empty() && !matches!(id.parse::<u64>(), Ok(id) if id > 0) {
+                problems.push(format!(
+                    "`territory_id` must be a positive number, got \"{id}\""
+                ));
+            }
+        }
+
+        if let Some(poptabs) = content.get("poptabs_received") {
+            if !poptabs.is_empty() && poptabs.parse::<f64>().is_err() {
+                problems.push(format!(
+                    "`poptabs_received` must be a number, got \"{poptabs}\""
+                ));
+            }
+        }
+
+        if *self == NotificationType::Custom
+            && ["title", "description"].iter().all(|field| {
+                content
+                    .get(*field)
+                    .map(|value| value.trim().is_empty())
+                    .unwrap_or(true)
+            })
+        {
+            problems.push("missing `title` or `description`".into());
+        }
+
+        if problems.is_empty() {
+            return Ok(());
+        }
+
+        Err(format!(
+            "Invalid \"{}\" XM8 notification: {}",
+            self.as_str(),
+            problems.join(", ")
+        ))
+    }
+}
+
+impl FromStr for NotificationType {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match NotificationType::ALL.iter().find(|t| t.as_str() == input) {
+            Some(notification_type) => Ok(*notification_type),
+            None => Err(format!(
+                "Unknown XM8 notification type \"{input}\". Expected one of: {}",
+                NotificationType::ALL
+                    .iter()
+                    .map(NotificationType::as_str)
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for NotificationType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn content(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn it_parses_every_type() {
+        for notification_type in NotificationType::ALL.iter().copied() {
+            assert_eq!(
+                notification_type.as_str().parse::<NotificationType>(),
+                Ok(notification_type)
+            );
+
+            assert_eq!(
+                serde_json::to_string(&notification_type).unwrap(),
+                format!("\"{notification_type}\"")
+            );
+        }
+
+        let error = "hacking-started".parse::<NotificationType>().unwrap_err();
+        assert!(error.contains("Unknown XM8 notification type \"hacking-started\""));
+    }
+
+    #[test]
+    fn it_validates_territory_notifications() {
+        let base_raid = NotificationType::BaseRaid;
+
+        assert!(base_raid
+            .validate(&content(&[
+                ("territory_id", "12"),
+                ("territory_name", "Home")
+            ]))
+            .is_ok());
+
+        assert_eq!(
+            base_raid.validate(&content(&[("territory_id", "-1")])),
+            Err("Invalid \"base-raid\" XM8 notification: missing `territory_name`, `territory_id` must be a positive number, got \"-1\"".into())
+        );
+    }
+
+    #[test]
+    fn it_validates_other_notifications() {
+        assert!(NotificationType::MarxetItemSold
+            .validate(&content(&[
+                ("item_name", "Ifrit"),
+                ("poptabs_received", "5000")
+            ]))
+            .is_ok());
+
+        assert!(NotificationType::MarxetItemSold
+            .validate(&content(&[
+                ("item_name", "Ifrit"),
+                ("poptabs_received", "lots")
+            ]))
+            .is_err());
+
+        assert!(NotificationType::Custom
+            .validate(&content(&[("title", "Hello")]))
+            .is_ok());
+
+        assert_eq!(
+            NotificationType::Custom.validate(&content(&[("title", " ")])),
+            Err("Invalid \"custom\" XM8 notification: missing `title` or `description`".into())
+        );
+    }
+}